@@ -0,0 +1,54 @@
+//! Compares read only algorithms on the adjacency list `Graph` and the frozen `CsrGraph`.
+//!
+//! Run with `cargo bench`, optionally followed by the number of nodes, e.g.
+//! `cargo bench -- 1000000`.  Each graph has five random edges per node.
+
+extern crate kyles_algorithm_crate;
+
+use std::env;
+use std::time::{Duration, Instant};
+
+use kyles_algorithm_crate::graph;
+
+// Share the seeded graph builder with the unit tests
+#[path = "../src/graph/test_util.rs"]
+#[allow(dead_code)]
+mod test_util;
+
+const EDGES_PER_NODE: u32 = 5;
+const RUNS: u32 = 5;
+
+/// Run `f` several times and return the fastest run
+fn time<F: FnMut() -> usize>(mut f: F) -> Duration{
+    let mut best = Duration::MAX;
+    let mut checksum = 0;
+
+    for _ in 0..RUNS{
+        let start = Instant::now();
+        checksum += f();
+        best = best.min(start.elapsed());
+    }
+
+    // Use the results so the work can not be optimised away
+    assert!(checksum > 0);
+    best
+}
+
+fn report(name: &str, list: Duration, csr: Duration){
+    println!("{:<10} {:>12.2?} {:>12.2?} {:>8.2}x", name, list, csr, list.as_secs_f64() / csr.as_secs_f64());
+}
+
+fn main(){
+    let n = env::args().skip(1).filter(|arg| !arg.starts_with('-')).filter_map(|arg| arg.parse().ok()).next().unwrap_or(200_000);
+
+    let graph = test_util::random_graph(n, n * EDGES_PER_NODE, 42, 1..101);
+    let copy = test_util::random_graph(n, n * EDGES_PER_NODE, 42, 1..101);
+    let start = Instant::now();
+    let frozen = copy.freeze();
+    println!("{} nodes, {} edges, frozen in {:.2?}", frozen.node_count(), frozen.edge_count(), start.elapsed());
+    println!("{:<10} {:>12} {:>12} {:>9}", "algorithm", "list", "csr", "speedup");
+
+    report("bfs", time(|| graph.bfs(0).count()), time(|| frozen.bfs(0).count()));
+    report("dijkstra", time(|| graph.dijkstra(0).distances.len()), time(|| frozen.dijkstra(0).distances.len()));
+    report("pagerank", time(|| graph.page_rank(0.85, 20).len()), time(|| frozen.page_rank(0.85, 20).len()));
+}
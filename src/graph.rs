@@ -1,28 +1,262 @@
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, Index, Sub};
+use std::slice;
+
+pub mod traversal;
+pub mod shortest_path;
+pub mod all_pairs;
+pub mod spanning_tree;
+pub mod topological;
+pub mod components;
+pub mod connectivity;
+pub mod flow;
+pub mod matching;
+pub mod assignment;
+pub mod eulerian;
+pub mod tsp;
+pub mod min_cut;
+pub mod page_rank;
+pub mod csr;
+pub mod matrix;
+#[cfg(test)]
+mod test_util;
+
+/// Adjacency list implementation of a graph, with nodes storing data of type `T` and edges
+/// weighted by values of type `E`.  `D` is either `Directed` or `Undirected`, so algorithms
+/// that only make sense for one kind of graph can only be called on that kind.
+pub struct Graph<T, E = i64, D = Directed>{
+    nodes: HashMap<u32, Node<T, E>>,
+    // The start node and ID of each edge going to each node ID, in the order they were added
+    incoming: HashMap<u32, Vec<(u32, EdgeId)>>,
+    // The ID the next added node will get.  IDs only ever go up, so removed IDs are not reused.
+    next_id: u32,
+    // The ID the next added edge will get
+    next_edge_id: u32,
+    direction: PhantomData<D>
+}
+
+/// Whether the edges of a graph have a direction
+pub trait Direction{
+    /// Returns true if edges only go from one node to another
+    fn is_directed() -> bool;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Marks a graph whose edges go from one node to another
+pub enum Directed{}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Marks a graph whose edges join two nodes both ways.  Each undirected edge has one ID and is
+/// kept in the edge list of both of its nodes, pointing away from that node.
+pub enum Undirected{}
+
+impl Direction for Directed{
+    fn is_directed() -> bool{
+        true
+    }
+}
+
+impl Direction for Undirected{
+    fn is_directed() -> bool{
+        false
+    }
+}
 
-/// Adjacency list implementation of a graph
-pub struct Graph<T>{
-    pub nodes: HashMap<u32, Node<T>>
+/// Read access to the nodes and edges of a graph, shared by `Graph`, `csr::CsrGraph` and
+/// `matrix::MatrixGraph` so that algorithms such as `all_pairs::floyd_warshall` can be written
+/// once and run on any of them.
+pub trait Adjacency{
+    /// The type of the values stored on the edges
+    type Payload;
+
+    /// Either `Directed` or `Undirected`
+    type Direction: Direction;
+
+    /// Returns the IDs of every node in the graph in ascending order
+    fn node_ids(&self) -> Vec<u32>;
+
+    /// Calls `visit` with the ID of the node each edge out of `node` goes to and the payload of
+    /// the edge, in the order the graph keeps its edges.  Edges to nodes that are not in the
+    /// graph are skipped.
+    /// # Arguments
+    ///
+    /// `node` - The ID of the node to visit the edges of
+    /// `visit` - Called once for each edge
+    fn for_each_edge<F: FnMut(u32, &Self::Payload)>(&self, node: u32, visit: F);
 }
 
 #[derive(Debug)]
 /// Nodes that store data
-pub struct Node<T>{
+pub struct Node<T, E = i64>{
     data: T,
-    edges: Vec<Edge>
+    edges: Vec<Edge<E>>
 }
 
 #[derive(Debug)]
 /// Directed edges between nodes
-pub struct Edge{
+pub struct Edge<E = i64>{
+    id: EdgeId,
     from: u32, 
     to: u32,
-    weight: u32
+    weight: E,
+    // The cost per unit of flow, for edges added to a flow network with one
+    cost: Option<E>
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// A unique ID for an edge in a graph.  IDs count up in the order edges are added and are
+/// never reused.
+pub struct EdgeId(u32);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Error returned when an edge can not be added to a graph
+pub enum GraphError{
+    /// The node with this ID is not in the graph
+    MissingNode(u32),
+    /// There is already an edge between these nodes
+    DuplicateEdge{
+        /// The ID of the node the edge starts at
+        from: u32,
+        /// The ID of the node the edge goes to
+        to: u32
+    },
+    /// The edge would start and end at the node with this ID
+    SelfLoopNotAllowed(u32)
+}
+
+impl fmt::Display for GraphError{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match *self{
+            GraphError::MissingNode(node) => write!(f, "node {} is not in the graph", node),
+            GraphError::DuplicateEdge{from, to} => write!(f, "there is already an edge from node {} to node {}", from, to),
+            GraphError::SelfLoopNotAllowed(node) => write!(f, "edge would loop from node {} back to itself", node)
+        }
+    }
+}
+
+impl Error for GraphError{}
+
+/// Numbers that can be used as edge weights by the shortest path and flow algorithms.  A weight
+/// needs a zero for empty paths, a one for edges added without a weight, addition and
+/// subtraction to build up and compare path lengths, and a partial order.
+///
+/// # Example
+/// ```
+/// # use kyles_algorithm_crate::graph::{Graph, Weight};
+/// use std::ops::{Add, Sub};
+///
+/// // A distance in whole metres
+/// #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+/// struct Metres(u32);
+///
+/// impl Add for Metres{
+///     type Output = Metres;
+///     fn add(self, other: Metres) -> Metres{ Metres(self.0 + other.0) }
+/// }
+///
+/// impl Sub for Metres{
+///     type Output = Metres;
+///     fn sub(self, other: Metres) -> Metres{ Metres(self.0 - other.0) }
+/// }
+///
+/// impl Weight for Metres{
+///     fn zero() -> Metres{ Metres(0) }
+///     fn one() -> Metres{ Metres(1) }
+/// }
+///
+/// // Create a graph
+/// let mut graph = Graph::new_weighted();
+///
+/// let zeroth_node = graph.add_node("home");
+/// let first_node = graph.add_node("shop");
+///
+/// graph.add_weighted_directed_edge(zeroth_node, first_node, Metres(250));
+///
+/// let paths = graph.dijkstra(zeroth_node);
+///
+/// assert_eq!(paths.distance_to(first_node), Some(Metres(250)));
+/// ```
+pub trait Weight: Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self>{
+    /// The weight of a path with no edges
+    fn zero() -> Self;
+
+    /// The weight given to edges that are added without one
+    fn one() -> Self;
+}
+
+macro_rules! impl_weight{
+    ($zero:expr, $one:expr, $($t:ty),*) => {
+        $(
+            impl Weight for $t{
+                fn zero() -> Self{
+                    $zero
+                }
+
+                fn one() -> Self{
+                    $one
+                }
+            }
+        )*
+    }
+}
+
+impl_weight!(0, 1, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_weight!(0.0, 1.0, f32, f64);
+
+/// Edge payloads the shortest path, spanning tree and flow algorithms can run on.  Every
+/// `Weight` is its own weight, so plain numbers can be used as payloads.  A payload that holds
+/// more than a weight, such as a label, says which part is the weight by implementing this.
+///
+/// # Example
+/// ```
+/// # use kyles_algorithm_crate::graph::{Graph, Weighted};
+/// // A named road with a length in metres
+/// struct Road{
+///     name: &'static str,
+///     length: u32
+/// }
+///
+/// impl Weighted for Road{
+///     type Weight = u32;
+///     fn weight(&self) -> u32{ self.length }
+/// }
+///
+/// // Create a graph
+/// let mut graph = Graph::new_weighted();
+///
+/// let zeroth_node = graph.add_node("home");
+/// let first_node = graph.add_node("shop");
+///
+/// graph.add_weighted_directed_edge(zeroth_node, first_node, Road{name: "High Street", length: 250});
+///
+/// let paths = graph.dijkstra(zeroth_node);
+///
+/// assert_eq!(paths.distance_to(first_node), Some(250));
+/// assert_eq!(graph.edges(zeroth_node).next().unwrap().weight().name, "High Street");
+/// ```
+pub trait Weighted{
+    /// The type of the weight
+    type Weight: Weight;
+
+    /// Returns the weight of the edge
+    fn weight(&self) -> Self::Weight;
+}
+
+impl<W: Weight> Weighted for W{
+    type Weight = W;
+
+    fn weight(&self) -> W{
+        *self
+    }
 }
 
 impl<T> Graph<T>{
 
-    /// Returns a new instance of a Graph
+    /// Returns a new instance of a directed Graph with `i64` edge weights.  Use
+    /// `new_undirected` for undirected graphs and `new_weighted` for other weight types.
     /// # Example
     ///
     /// ```
@@ -31,10 +265,47 @@ impl<T> Graph<T>{
     /// let graph: Graph<u32> = Graph::new();
     /// ``` 
     pub fn new() -> Self{
-        Graph{nodes: HashMap::new()}
+        Graph::new_weighted()
+    }
+}
+
+impl<T> Graph<T, i64, Undirected>{
+
+    /// Returns a new instance of an undirected Graph with `i64` edge weights
+    /// # Example
+    ///
+    /// ```
+    /// # use kyles_algorithm_crate::graph::{Graph, Undirected};
+    /// // Creates a new undirected graph
+    /// let graph: Graph<u32, i64, Undirected> = Graph::new_undirected();
+    /// ```
+    pub fn new_undirected() -> Self{
+        Graph::new_weighted()
+    }
+}
+
+impl<T, E, D: Direction> Graph<T, E, D>{
+
+    /// Returns a new instance of a Graph with edge weights of any type, either directed or
+    /// undirected
+    /// # Example
+    ///
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Creates a new graph with floating point weights
+    /// let mut graph: Graph<&str, f64> = Graph::new_weighted();
+    ///
+    /// let node1 = graph.add_node("a");
+    /// let node2 = graph.add_node("b");
+    ///
+    /// graph.add_weighted_directed_edge(node1, node2, 0.5);
+    /// ``` 
+    pub fn new_weighted() -> Self{
+        Graph{nodes: HashMap::new(), incoming: HashMap::new(), next_id: 0, next_edge_id: 0, direction: PhantomData}
     }
 
     /// Add a new node to the graph with supplied data.  Returns the id of the node in the graph.
+    /// IDs count up from 0 and are never reused, even after a node is removed.
     /// # Arguments
     /// 
     /// `data` - The data to put in the node to be added
@@ -53,10 +324,11 @@ impl<T> Graph<T>{
     /// ``` 
     pub fn add_node(&mut self, data: T) -> u32{
 
-        // The new node id will be the current number of nodes in the graph
-        let node_id = self.nodes.len() as u32;
+        // The new node id will be the next one that has never been handed out
+        let node_id = self.next_id;
+        self.next_id += 1;
 
-        let new_node = Node::new(data);
+        let new_node = Node{data, edges: Vec::new()};
 
         // Insert the node
         self.nodes.insert(node_id, new_node);
@@ -65,11 +337,96 @@ impl<T> Graph<T>{
         node_id
     }
 
-    /// Add a directed edge from one node to another
+    /// Remove a node from the graph along with every edge going into or out of it.  Returns the
+    /// data of the removed node, or `None` if it was not in the graph.  The ID of the removed
+    /// node is never given to a new node.
     /// # Arguments
     ///
-    /// `from` - The ID of the  node the edge will start at
-    /// `to` - The ID of the node the edge will go to
+    /// `node` - The ID of the node to remove
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// // Add two nodes
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// graph.add_undirected_edge(node1, node2);
+    ///
+    /// assert_eq!(graph.remove_node(node1), Some(1));
+    /// assert_eq!(graph.out_degree(node2), Some(0));
+    ///
+    /// // The next node gets a new ID
+    /// let node3 = graph.add_node(3);
+    ///
+    /// assert_ne!(node3, node1);
+    /// ```
+    pub fn remove_node(&mut self, node: u32) -> Option<T>{
+        let removed = self.nodes.remove(&node)?;
+
+        // Other nodes may still have edges to the removed node
+        for (from, id) in self.incoming.remove(&node).unwrap_or_default(){
+            if let Some(other) = self.nodes.get_mut(&from){
+                other.edges.retain(|edge| edge.id != id);
+            }
+        }
+
+        for edge in &removed.edges{
+            if edge.to != node{
+                self.unindex_edge(edge.to, edge.id);
+            }
+        }
+
+        Some(removed.data)
+    }
+
+    /// Remove one edge from one node to another.  If there are several such edges the one added
+    /// first is removed.  Returns the weight of the removed edge, or `None` if there was no edge.
+    /// Removing an edge of an undirected graph removes it from both ends.
+    /// # Arguments
+    ///
+    /// `from` - The ID of the node the edge starts at
+    /// `to` - The ID of the node the edge goes to
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// // Add two nodes
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// graph.add_weighted_directed_edge(node1, node2, 10);
+    ///
+    /// assert_eq!(graph.remove_edge(node1, node2), Some(10));
+    /// assert_eq!(graph.remove_edge(node1, node2), None);
+    /// ```
+    pub fn remove_edge(&mut self, from: u32, to: u32) -> Option<E>{
+        let edges = &mut self.nodes.get_mut(&from)?.edges;
+        let position = edges.iter().position(|edge| edge.to == to)?;
+        let removed = edges.remove(position);
+
+        self.unindex_edge(to, removed.id);
+
+        // The other end of an undirected edge has the same ID
+        if !D::is_directed() && from != to{
+            if let Some(back) = self.nodes.get_mut(&to){
+                back.edges.retain(|edge| edge.id != removed.id);
+                self.unindex_edge(from, removed.id);
+            }
+        }
+
+        Some(removed.weight)
+    }
+
+    /// Get a node from the graph
+    /// # Arguments
+    /// 'node' - The ID of the node to get from the graph
     ///
     /// # Example
     /// ```
@@ -81,25 +438,383 @@ impl<T> Graph<T>{
     /// let node1 = graph.add_node(1);
     /// let node2 = graph.add_node(2);
     ///
-    /// // Add an edge from node1 to node 2
+    /// // get node 1
+    /// let received_node = graph.get_node(node1);
+    /// ```
+    pub fn get_node(&self, node: u32) -> Option<&Node<T, E>>{
+        self.nodes.get(&node)
+    }
+
+    /// Get a mutable node from the graph
+    /// # Arguments
+    /// 'node' - The ID of the node to get from the graph
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    /// 
+    /// // Add two nodes
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// // get node 1
+    /// let received_node = graph.get_node_mut(node1);
+    /// ```
+    pub fn get_node_mut(&mut self, node: u32) -> Option<&mut Node<T, E>>{
+
+        if let Some(res) = self.nodes.get_mut(&node){
+            Some(res)
+        }else{
+            None
+        }
+    }
+
+    /// Returns the IDs of every node in the graph in ascending order
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// // Add three nodes and remove one
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.remove_node(node2);
+    ///
+    /// assert_eq!(graph.node_ids(), vec![node1, node3]);
+    /// ```
+    pub fn node_ids(&self) -> Vec<u32>{
+        self.sorted_node_ids()
+    }
+
+    /// Returns the number of nodes in the graph
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// graph.add_node(1);
+    /// graph.add_node(2);
+    ///
+    /// assert_eq!(graph.node_count(), 2);
+    /// ```
+    pub fn node_count(&self) -> usize{
+        self.nodes.len()
+    }
+
+    /// Returns true if a node with the given ID is in the graph
+    /// # Arguments
+    /// `node` - The ID of the node to look for
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// graph.remove_node(node2);
+    ///
+    /// assert!(graph.contains_node(node1));
+    /// assert!(!graph.contains_node(node2));
+    /// ```
+    pub fn contains_node(&self, node: u32) -> bool{
+        self.nodes.contains_key(&node)
+    }
+
+    /// Returns the number of edges in the graph.  An undirected edge counts once, even though
+    /// both of its nodes keep it.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// graph.add_undirected_edge(node1, node2);
+    /// graph.add_undirected_edge(node2, node2);
+    ///
+    /// assert_eq!(graph.edge_count(), 2);
+    /// ```
+    pub fn edge_count(&self) -> usize{
+        if D::is_directed(){
+            self.nodes.values().map(|node| node.edges.len()).sum()
+        }else{
+            // Only count each edge at the end with the smaller ID
+            self.nodes.values().map(|node| node.edges.iter().filter(|edge| edge.from <= edge.to).count()).sum()
+        }
+    }
+
+    /// Returns an iterator over the edges going out of a node in the order they were added,
+    /// which is empty if the node is not in the graph.  In an undirected graph these are the
+    /// edges touching the node, each pointing away from it.
+    /// # Arguments
+    /// `node` - The ID of the node to get the edges of
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// graph.add_weighted_directed_edge(node1, node2, 5);
+    ///
+    /// let edge = graph.edges(node1).next().unwrap();
+    ///
+    /// assert_eq!((edge.from(), edge.to(), *edge.weight()), (node1, node2, 5));
+    /// assert_eq!(graph.edges(node2).count(), 0);
+    /// ```
+    pub fn edges(&self, node: u32) -> slice::Iter<'_, Edge<E>>{
+        match self.nodes.get(&node){
+            Some(node) => node.edges.iter(),
+            None => [].iter()
+        }
+    }
+
+    /// Returns an iterator over the IDs of the nodes the edges of a node go to, in the order the
+    /// edges were added.  A neighbour is repeated once for each parallel edge.  The iterator is
+    /// empty if the node is not in the graph.
+    /// # Arguments
+    /// `node` - The ID of the node to get the neighbours of
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_undirected_edge(node1, node2);
+    /// graph.add_undirected_edge(node3, node1);
+    ///
+    /// assert_eq!(graph.neighbors(node1).collect::<Vec<u32>>(), vec![node2, node3]);
+    /// assert_eq!(graph.neighbors(node2).collect::<Vec<u32>>(), vec![node1]);
+    /// ```
+    pub fn neighbors(&self, node: u32) -> Neighbors<'_, E>{
+        Neighbors{edges: self.edges(node)}
+    }
+
+    /// Get the number of edges going out of a node, or `None` if the node is not in the graph.
+    /// In an undirected graph this is the number of edges touching the node, with self loops
+    /// counted once.
+    /// # Arguments
+    /// `node` - The ID of the node to count the edges of
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// // Add two nodes
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
     /// graph.add_directed_edge(node1, node2);
     ///
+    /// assert_eq!(graph.out_degree(node1), Some(1));
+    /// assert_eq!(graph.out_degree(node2), Some(0));
+    /// assert_eq!(graph.out_degree(7), None);
     /// ```
-    pub fn add_directed_edge(&mut self, from: u32, to: u32){
-        // Create a new edge with weight 1
-        let edge = Edge::new(from, to, 1);
+    pub fn out_degree(&self, node: u32) -> Option<usize>{
+        self.nodes.get(&node).map(|n| n.edges.len())
+    }
 
-        // Grab the from node and add the edge to its list of edges
-        if let Some(n) = self.nodes.get_mut(&from){
-            n.edges.push(edge);
+    /// Get the number of edges coming into a node, or `None` if the node is not in the graph.
+    /// In an undirected graph this is the same as the out-degree.
+    /// # Arguments
+    /// `node` - The ID of the node to count the edges of
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// // Add two nodes
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// graph.add_directed_edge(node1, node2);
+    ///
+    /// assert_eq!(graph.in_degree(node1), Some(0));
+    /// assert_eq!(graph.in_degree(node2), Some(1));
+    /// assert_eq!(graph.in_degree(7), None);
+    /// ```
+    pub fn in_degree(&self, node: u32) -> Option<usize>{
+        if self.nodes.contains_key(&node){
+            Some(self.incoming.get(&node).map_or(0, |incoming| incoming.len()))
+        }else{
+            None
         }
     }
 
-    /// Add an undirected edge between two nodes
+    /// Get the edges coming into a node in the order they were added, or `None` if the node is
+    /// not in the graph.  In an undirected graph these are the edges touching the node, each
+    /// seen from its other end.
     /// # Arguments
+    /// `node` - The ID of the node to get the edges of
     ///
-    /// `node1` - The ID of the first node to add an edge to
-    /// `node2` - The ID of the second node to add an edge to
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// // Add three nodes
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// let edge1 = graph.try_add_directed_edge(node1, node3).unwrap();
+    /// let edge2 = graph.try_add_directed_edge(node2, node3).unwrap();
+    ///
+    /// let ids: Vec<_> = graph.in_edges(node3).unwrap().iter().map(|edge| edge.id()).collect();
+    ///
+    /// assert_eq!(ids, vec![edge1, edge2]);
+    /// assert!(graph.in_edges(node1).unwrap().is_empty());
+    /// assert!(graph.in_edges(7).is_none());
+    /// ```
+    pub fn in_edges(&self, node: u32) -> Option<Vec<&Edge<E>>>{
+        if !self.nodes.contains_key(&node){
+            return None;
+        }
+
+        let incoming = match self.incoming.get(&node){
+            Some(incoming) => incoming,
+            None => return Some(Vec::new())
+        };
+
+        // Both halves of an undirected edge share an ID, so also match the end
+        Some(incoming.iter().map(|&(from, id)| {
+            self.nodes[&from].edges.iter().find(|edge| edge.id == id && edge.to == node).unwrap()
+        }).collect())
+    }
+
+    /// Get the sorted, distinct IDs of the nodes with an edge into a node, or `None` if the node
+    /// is not in the graph.  In an undirected graph these are the neighbours of the node.
+    /// # Arguments
+    /// `node` - The ID of the node to get the predecessors of
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// // Add three nodes
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node2, node1);
+    /// graph.add_directed_edge(node3, node1);
+    /// graph.add_directed_edge(node2, node1);
+    ///
+    /// assert_eq!(graph.predecessors(node1), Some(vec![node2, node3]));
+    /// assert_eq!(graph.predecessors(node2), Some(vec![]));
+    /// ```
+    pub fn predecessors(&self, node: u32) -> Option<Vec<u32>>{
+        if !self.nodes.contains_key(&node){
+            return None;
+        }
+
+        let mut predecessors: Vec<u32> = self.incoming.get(&node).map_or(Vec::new(), |incoming| incoming.iter().map(|&(from, _)| from).collect());
+        predecessors.sort();
+        predecessors.dedup();
+
+        Some(predecessors)
+    }
+
+    /// Give an edge the next edge ID and add it to the graph.  Returns the ID of the edge, or
+    /// `None` if either node is not in the graph.  Node IDs are never reused, so an edge to a
+    /// missing node could never be followed.
+    fn push_edge(&mut self, mut edge: Edge<E>) -> Option<EdgeId>{
+        if !self.nodes.contains_key(&edge.from) || !self.nodes.contains_key(&edge.to){
+            return None;
+        }
+
+        edge.id = EdgeId(self.next_edge_id);
+        self.next_edge_id += 1;
+
+        let id = edge.id;
+        self.insert_edge(edge);
+
+        Some(id)
+    }
+
+    /// Add an edge to the list of the node it starts at and the incoming edges of the node it
+    /// goes to.  The starting node must be in the graph.
+    fn insert_edge(&mut self, edge: Edge<E>){
+        self.incoming.entry(edge.to).or_default().push((edge.from, edge.id));
+        self.nodes.get_mut(&edge.from).unwrap().edges.push(edge);
+    }
+
+    /// Check that both nodes are in the graph and an edge between them would be neither a self
+    /// loop nor a second edge in the same direction
+    fn check_new_edge(&self, from: u32, to: u32) -> Result<(), GraphError>{
+        let from_node = self.nodes.get(&from).ok_or(GraphError::MissingNode(from))?;
+
+        if !self.nodes.contains_key(&to){
+            return Err(GraphError::MissingNode(to));
+        }
+
+        if from == to{
+            return Err(GraphError::SelfLoopNotAllowed(from));
+        }
+
+        if from_node.edges.iter().any(|edge| edge.to == to){
+            return Err(GraphError::DuplicateEdge{from, to});
+        }
+
+        Ok(())
+    }
+
+    /// Remove an edge from the incoming edges of the node it goes to
+    fn unindex_edge(&mut self, node: u32, id: EdgeId){
+        if let Some(incoming) = self.incoming.get_mut(&node){
+            incoming.retain(|&(_, edge_id)| edge_id != id);
+
+            if incoming.is_empty(){
+                self.incoming.remove(&node);
+            }
+        }
+    }
+
+    /// Returns the IDs of every node in the graph in ascending order, so algorithms
+    /// visit nodes in a fixed order that does not depend on hashing
+    pub(crate) fn sorted_node_ids(&self) -> Vec<u32>{
+        let mut node_ids: Vec<u32> = self.nodes.keys().cloned().collect();
+        node_ids.sort();
+        node_ids
+    }
+}
+
+impl<T, E> Graph<T, E, Directed>{
+
+    /// Add a directed edge from one node to another.  Nothing is added if either node is missing.
+    /// # Arguments
+    ///
+    /// `from` - The ID of the  node the edge will start at
+    /// `to` - The ID of the node the edge will go to
     ///
     /// # Example
     /// ```
@@ -112,22 +827,24 @@ impl<T> Graph<T>{
     /// let node2 = graph.add_node(2);
     ///
     /// // Add an edge from node1 to node 2
-    /// graph.add_undirected_edge(node1, node2);
+    /// graph.add_directed_edge(node1, node2);
+    ///
     /// ```
-    pub fn add_undirected_edge(&mut self, node1: u32, node2: u32){
-        // Add directed edge from node1 to node2
-        self.add_directed_edge(node1, node2);
+    pub fn add_directed_edge(&mut self, from: u32, to: u32) where E: Weight{
+        // Create a new edge with weight 1
+        let edge = Edge::new(from, to, E::one());
 
-        // Add directed edge from node2 to node1
-        self.add_directed_edge(node2, node1);
+        // Grab the from node and add the edge to its list of edges
+        self.push_edge(edge);
     }
 
-    /// Add a weighted directed edge from one node to another
+    /// Add a weighted directed edge from one node to another.  Nothing is added if either node
+    /// is missing.
     /// # Arguments
     ///
     /// `from` - The ID of the node the edge will come from
     /// `to` - The ID of the node the edge will go to
-    /// `weight` - The weight of the edge
+    /// `weight` - The weight of the edge, which may be negative
     ///
     /// # Example
     /// ```
@@ -142,131 +859,439 @@ impl<T> Graph<T>{
     /// // Add an edge of weight 10 from node1 to node 2
     /// graph.add_weighted_directed_edge(node1, node2, 10);
     /// ```
-    pub fn add_weighted_directed_edge(&mut self, from: u32, to: u32, weight: u32){
+    pub fn add_weighted_directed_edge(&mut self, from: u32, to: u32, weight: E){
         // Create a new edge
         let edge = Edge::new(from, to, weight);
 
-        // Get the from node from the hashmap
-        if let Some(n) = self.nodes.get_mut(&from){
-            n.edges.push(edge);
-        }
+        // Get the from node from the hashmap
+        self.push_edge(edge);
+    }
+
+    /// Add a directed edge for a flow network, with a capacity and a cost per unit of flow.
+    /// The capacity is used as the weight of the edge.  Nothing is added if either node is
+    /// missing.
+    /// # Arguments
+    ///
+    /// `from` - The ID of the node the edge will come from
+    /// `to` - The ID of the node the edge will go to
+    /// `capacity` - The most flow the edge can carry
+    /// `cost` - The cost of sending one unit of flow along the edge
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    /// 
+    /// // Add two nodes
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// // Add an edge from node1 to node 2 that carries up to 10 units at a cost of 3 each
+    /// graph.add_costed_directed_edge(node1, node2, 10, 3);
+    /// ```
+    pub fn add_costed_directed_edge(&mut self, from: u32, to: u32, capacity: E, cost: E){
+        let edge = Edge::with_cost(from, to, capacity, cost);
+
+        self.push_edge(edge);
+    }
+
+    /// Add a directed edge from one node to another, checking the input first.  Unlike
+    /// `add_directed_edge`, this fails instead of quietly skipping bad edges, and it keeps the
+    /// graph simple by refusing self loops and parallel edges.  Returns the ID of the new edge.
+    /// # Arguments
+    ///
+    /// `from` - The ID of the node the edge will start at
+    /// `to` - The ID of the node the edge will go to
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::{Graph, GraphError};
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// // Add two nodes
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// assert!(graph.try_add_directed_edge(node1, node2).is_ok());
+    ///
+    /// assert_eq!(graph.try_add_directed_edge(node1, node2), Err(GraphError::DuplicateEdge{from: node1, to: node2}));
+    /// assert_eq!(graph.try_add_directed_edge(node1, 7), Err(GraphError::MissingNode(7)));
+    /// assert_eq!(graph.try_add_directed_edge(node2, node2), Err(GraphError::SelfLoopNotAllowed(node2)));
+    /// ```
+    pub fn try_add_directed_edge(&mut self, from: u32, to: u32) -> Result<EdgeId, GraphError> where E: Weight{
+        self.try_add_weighted_directed_edge(from, to, E::one())
+    }
+
+    /// Add a weighted directed edge from one node to another, checking the input first.  Fails
+    /// if either node is missing, the edge is a self loop or there is already an edge from
+    /// `from` to `to`.  Returns the ID of the new edge.
+    /// # Arguments
+    ///
+    /// `from` - The ID of the node the edge will come from
+    /// `to` - The ID of the node the edge will go to
+    /// `weight` - The weight of the edge, which may be negative
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// // Add two nodes
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// let first = graph.try_add_weighted_directed_edge(node1, node2, 10).unwrap();
+    /// let second = graph.try_add_weighted_directed_edge(node2, node1, 10).unwrap();
+    ///
+    /// assert!(first < second);
+    /// ```
+    pub fn try_add_weighted_directed_edge(&mut self, from: u32, to: u32, weight: E) -> Result<EdgeId, GraphError>{
+        self.check_new_edge(from, to)?;
+
+        Ok(self.push_edge(Edge::new(from, to, weight)).unwrap())
+    }
+
+    /// Add a directed edge for a flow network with a capacity and a cost per unit of flow,
+    /// checking the input first.  Fails if either node is missing, the edge is a self loop or
+    /// there is already an edge from `from` to `to`.  Returns the ID of the new edge.
+    /// # Arguments
+    ///
+    /// `from` - The ID of the node the edge will come from
+    /// `to` - The ID of the node the edge will go to
+    /// `capacity` - The most flow the edge can carry
+    /// `cost` - The cost of sending one unit of flow along the edge
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::{Graph, GraphError};
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    ///
+    /// assert_eq!(graph.try_add_costed_directed_edge(node1, 7, 10, 3), Err(GraphError::MissingNode(7)));
+    /// ```
+    pub fn try_add_costed_directed_edge(&mut self, from: u32, to: u32, capacity: E, cost: E) -> Result<EdgeId, GraphError>{
+        self.check_new_edge(from, to)?;
+
+        Ok(self.push_edge(Edge::with_cost(from, to, capacity, cost)).unwrap())
+    }
+}
+
+impl<T, E> Graph<T, E, Undirected>{
+
+    /// Add an undirected edge between two nodes.  The edge is one edge with one ID that can be
+    /// followed from either end.  Nothing is added if either node is missing.
+    /// # Arguments
+    ///
+    /// `node1` - The ID of the first node to add an edge to
+    /// `node2` - The ID of the second node to add an edge to
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    /// 
+    /// // Add two nodes
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// // Add an edge between node1 and node 2
+    /// graph.add_undirected_edge(node1, node2);
+    ///
+    /// assert_eq!(graph.out_degree(node2), Some(1));
+    /// ```
+    pub fn add_undirected_edge(&mut self, node1: u32, node2: u32) where E: Weight{
+        self.push_undirected_edge(node1, node2, E::one());
+    }
+
+    /// Add a weighted undirected edge between two nodes.  Nothing is added if either node is
+    /// missing.
+    /// # Arguments
+    /// 
+    /// `node1` - The ID of the first node to add the edge to
+    /// `node2` - The ID of the second node to add the edge to
+    /// `weight` - The weight for the edge
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    /// 
+    /// // Add two nodes
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// // Add an edge of weight 10 between node1 and node 2
+    /// graph.add_weighted_undirected_edge(node1, node2, 10);
+    /// ```
+    pub fn add_weighted_undirected_edge(&mut self, node1: u32, node2: u32, weight: E) where E: Clone{
+        self.push_undirected_edge(node1, node2, weight);
+    }
+
+    /// Add an undirected edge between two nodes, checking the input first.  Fails if either
+    /// node is missing, the edge is a self loop or the nodes are already joined by an edge.
+    /// Returns the ID of the new edge.
+    /// # Arguments
+    ///
+    /// `node1` - The ID of the first node to add an edge to
+    /// `node2` - The ID of the second node to add an edge to
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::{Graph, GraphError};
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// // Add two nodes
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// assert!(graph.try_add_undirected_edge(node2, node1).is_ok());
+    ///
+    /// assert_eq!(graph.try_add_undirected_edge(node1, node2), Err(GraphError::DuplicateEdge{from: node1, to: node2}));
+    /// assert_eq!(graph.out_degree(node1), Some(1));
+    /// ```
+    pub fn try_add_undirected_edge(&mut self, node1: u32, node2: u32) -> Result<EdgeId, GraphError> where E: Weight{
+        self.try_add_weighted_undirected_edge(node1, node2, E::one())
+    }
+
+    /// Add a weighted undirected edge between two nodes, checking the input first.  Fails if
+    /// either node is missing, the edge is a self loop or the nodes are already joined by an
+    /// edge.  Returns the ID of the new edge.
+    /// # Arguments
+    ///
+    /// `node1` - The ID of the first node to add the edge to
+    /// `node2` - The ID of the second node to add the edge to
+    /// `weight` - The weight for the edge
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// // Add three nodes
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// let first = graph.try_add_weighted_undirected_edge(node1, node2, 10).unwrap();
+    /// let second = graph.try_add_weighted_undirected_edge(node2, node3, 10).unwrap();
+    ///
+    /// assert!(first < second);
+    /// ```
+    pub fn try_add_weighted_undirected_edge(&mut self, node1: u32, node2: u32, weight: E) -> Result<EdgeId, GraphError> where E: Clone{
+        self.check_new_edge(node1, node2)?;
+
+        Ok(self.push_undirected_edge(node1, node2, weight).unwrap())
+    }
+
+    /// Add an undirected edge under a single new edge ID, stored once in the edge list of each
+    /// end so it can be followed either way.  A self loop is stored once.  Returns the ID of the
+    /// edge, or `None` if either node is not in the graph.
+    fn push_undirected_edge(&mut self, node1: u32, node2: u32, weight: E) -> Option<EdgeId> where E: Clone{
+        if !self.nodes.contains_key(&node1) || !self.nodes.contains_key(&node2){
+            return None;
+        }
+
+        let id = self.push_edge(Edge::new(node1, node2, weight.clone()))?;
+
+        if node1 != node2{
+            let mut back = Edge::new(node2, node1, weight);
+            back.id = id;
+            self.insert_edge(back);
+        }
+
+        Some(id)
+    }
+}
+
+impl<T, E, D: Direction> Default for Graph<T, E, D>{
+    fn default() -> Self{
+        Graph::new_weighted()
+    }
+}
+
+impl<T, E, D: Direction> Adjacency for Graph<T, E, D>{
+    type Payload = E;
+    type Direction = D;
+
+    fn node_ids(&self) -> Vec<u32>{
+        self.sorted_node_ids()
+    }
+
+    fn for_each_edge<F: FnMut(u32, &E)>(&self, node: u32, mut visit: F){
+        for edge in self.edges(node){
+            if self.nodes.contains_key(&edge.to){
+                visit(edge.to, &edge.weight);
+            }
+        }
+    }
+}
+
+impl<T, E, D: Direction> Index<u32> for Graph<T, E, D>{
+    type Output = T;
+
+    /// Returns the data of the node with the given ID
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in the graph
+    fn index(&self, node: u32) -> &T{
+        match self.nodes.get(&node){
+            Some(node) => &node.data,
+            None => panic!("node {} is not in the graph", node)
+        }
+    }
+}
+
+/// Iterator over the IDs of the nodes the edges of a node go to, made by `Graph::neighbors`
+pub struct Neighbors<'a, E: 'a = i64>{
+    edges: slice::Iter<'a, Edge<E>>
+}
+
+impl<'a, E> Iterator for Neighbors<'a, E>{
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32>{
+        self.edges.next().map(|edge| edge.to)
     }
+}
+
+impl<T> Node<T>{
 
-    /// Add a weighted undirected edge between two nodes
+    /// Returns a new instance of a Node with the given data
     /// # Arguments
-    /// 
-    /// `node1` - The ID of the first node to add the edge to
-    /// `node2` - The ID of the second node to add the edge to
-    /// `weight` - The weight for the edge
+    ///
+    /// * `data` - The data to store in the node 
     ///
     /// # Example
-    /// ```
-    /// # use kyles_algorithm_crate::graph::Graph;
-    /// // Create a graph
-    /// let mut graph = Graph::new();
-    /// 
-    /// // Add two nodes
-    /// let node1 = graph.add_node(1);
-    /// let node2 = graph.add_node(2);
     ///
-    /// // Add an edge of weight 10 from node1 to node 2
-    /// graph.add_weighted_undirected_edge(node1, node2, 10);
     /// ```
-    pub fn add_weighted_undirected_edge(&mut self, node1: u32, node2: u32, weight: u32){
-        // Add directed edge from node1 to node2
-        self.add_weighted_directed_edge(node1, node2, weight);
-
-        // Add directed edge from node2 to node1
-        self.add_weighted_directed_edge(node2, node1, weight);
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// # use kyles_algorithm_crate::graph::Node;
+    /// // Creates a new node
+    /// let node = Node::new(10);
+    /// ``` 
+    pub fn new(data: T) -> Self{
+        Node{data, edges: Vec::new()}
     }
+}
 
-    /// Get a node from the graph
-    /// # Arguments
-    /// 'node' - The ID of the node to get from the graph
+impl<T, E> Node<T, E>{
+
+    /// Returns the data stored in the node
     ///
     /// # Example
     /// ```
     /// # use kyles_algorithm_crate::graph::Graph;
     /// // Create a graph
     /// let mut graph = Graph::new();
-    /// 
-    /// // Add two nodes
-    /// let node1 = graph.add_node(1);
-    /// let node2 = graph.add_node(2);
     ///
-    /// // get node 1
-    /// let received_node = graph.get_node(node1);
+    /// let node1 = graph.add_node("a");
+    ///
+    /// assert_eq!(*graph.get_node(node1).unwrap().data(), "a");
     /// ```
-    pub fn get_node(&self, node: u32) -> Option<&Node<T>>{
-        self.nodes.get(&node)
+    pub fn data(&self) -> &T{
+        &self.data
     }
 
-    /// Get a mutable node from the graph
-    /// # Arguments
-    /// 'node' - The ID of the node to get from the graph
+    /// Returns the data stored in the node for changing
     ///
     /// # Example
     /// ```
     /// # use kyles_algorithm_crate::graph::Graph;
     /// // Create a graph
     /// let mut graph = Graph::new();
-    /// 
-    /// // Add two nodes
+    ///
     /// let node1 = graph.add_node(1);
-    /// let node2 = graph.add_node(2);
     ///
-    /// // get node 1
-    /// let received_node = graph.get_node_mut(node1);
+    /// *graph.get_node_mut(node1).unwrap().data_mut() += 1;
+    ///
+    /// assert_eq!(graph[node1], 2);
     /// ```
-    pub fn get_node_mut(&mut self, node: u32) -> Option<&mut Node<T>>{
+    pub fn data_mut(&mut self) -> &mut T{
+        &mut self.data
+    }
 
-        if let Some(res) = self.nodes.get_mut(&node){
-            Some(res)
-        }else{
-            None
-        }
+    /// Returns the edges going out of the node in the order they were added
+    pub fn edges(&self) -> &[Edge<E>]{
+        &self.edges
     }
 }
 
-impl<T> Node<T>{
+impl<E> Edge<E>{
 
-    /// Returns a new instance of a Node with the given data
+    /// Returns a new instance of an edge from one node to another, with a given weight
     /// # Arguments
     ///
-    /// * `data` - The data to store in the node 
+    /// * `from` - The ID of the node this edge starts from
+    /// * `to`   - The ID of the node this edge goes to
+    /// * `weight` - The weight of the edge 
     ///
     /// # Example
     ///
     /// ```
     /// # use kyles_algorithm_crate::graph::Graph;
-    /// # use kyles_algorithm_crate::graph::Node;
-    /// // Creates a new node
-    /// let node = Node::new(10);
+    /// # use kyles_algorithm_crate::graph::Edge;
+    /// // Creates a new edge
+    /// let edge = Edge::new(1, 2, 10);
     /// ``` 
-    pub fn new(data: T) -> Self{
-        Node{data: data, edges: Vec::new()}
+    pub fn new(from: u32, to: u32, weight: E) -> Self{
+        Edge{id: EdgeId(0), from, to, weight, cost: None}
     }
-}
 
-impl Edge{
-
-    /// Returns a new instance of an edge from one node to another, with a given weight
+    /// Returns a new instance of an edge from one node to another, with a capacity and a cost
+    /// per unit of flow for use in flow networks.  The capacity is stored as the weight.
     /// # Arguments
     ///
     /// * `from` - The ID of the node this edge starts from
     /// * `to`   - The ID of the node this edge goes to
-    /// * `weight` - The weight of the edge 
+    /// * `capacity` - The most flow the edge can carry
+    /// * `cost` - The cost of sending one unit of flow along the edge
     ///
     /// # Example
     ///
     /// ```
-    /// # use kyles_algorithm_crate::graph::Graph;
     /// # use kyles_algorithm_crate::graph::Edge;
-    /// // Creates a new edge
-    /// let edge = Edge::new(1, 2, 10);
-    /// ``` 
-    pub fn new(from: u32, to: u32, weight: u32) -> Self{
-        Edge{from: from, to: to, weight: weight}
+    /// // Creates a new edge with capacity 10 and cost 3
+    /// let edge = Edge::with_cost(1, 2, 10, 3);
+    /// ```
+    pub fn with_cost(from: u32, to: u32, capacity: E, cost: E) -> Self{
+        Edge{id: EdgeId(0), from, to, weight: capacity, cost: Some(cost)}
+    }
+
+    /// Returns the ID the edge was given when it was added to a graph
+    pub fn id(&self) -> EdgeId{
+        self.id
+    }
+
+    /// Returns the ID of the node the edge starts at
+    pub fn from(&self) -> u32{
+        self.from
+    }
+
+    /// Returns the ID of the node the edge goes to
+    pub fn to(&self) -> u32{
+        self.to
+    }
+
+    /// Returns the weight of the edge, which is its capacity in a flow network.  For graphs
+    /// whose edges hold more than a weight, this is the whole payload.
+    pub fn weight(&self) -> &E{
+        &self.weight
+    }
+
+    /// Returns the cost of sending one unit of flow along the edge, or `None` if the edge was
+    /// added without a cost
+    pub fn cost(&self) -> Option<&E>{
+        self.cost.as_ref()
     }
 }
 
@@ -406,7 +1431,7 @@ mod tests {
     fn test_add_undirected_edge(){
         use graph::Graph;
 
-        let mut graph = Graph::new();
+        let mut graph = Graph::new_undirected();
 
         let zeroth_node = graph.add_node(0);
         let first_node = graph.add_node(1);
@@ -427,6 +1452,8 @@ mod tests {
         assert_eq!(node2.edges[0].to, zeroth_node);
         assert_eq!(node2.edges[0].weight, 1);
 
+        // Both ends hold the same edge
+        assert_eq!(node.edges[0].id(), node2.edges[0].id());
     }
 
     #[test]
@@ -480,7 +1507,7 @@ mod tests {
     fn test_add_weighted_undirected_edge(){
         use graph::Graph;
 
-        let mut graph = Graph::new();
+        let mut graph = Graph::new_undirected();
 
         let zeroth_node = graph.add_node(0);
         let first_node = graph.add_node(1);
@@ -502,4 +1529,332 @@ mod tests {
         assert_eq!(node2.edges[0].weight, 15);
 
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_add_costed_directed_edge(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+
+        graph.add_costed_directed_edge(zeroth_node, first_node, 15, 4);
+        graph.add_weighted_directed_edge(first_node, zeroth_node, 20);
+
+        let graph_nodes = &mut graph.nodes;
+
+        let node = &graph_nodes.get(&zeroth_node).unwrap();
+
+        assert_eq!(node.edges.len(), 1);
+        assert_eq!(node.edges[0].to, first_node);
+        assert_eq!(node.edges[0].weight, 15);
+        assert_eq!(node.edges[0].cost, Some(4));
+
+        let node2 = graph_nodes.get(&first_node).unwrap();
+
+        assert_eq!(node2.edges.len(), 1);
+        assert_eq!(node2.edges[0].cost, None);
+    }
+
+    #[test]
+    fn test_degrees(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_directed_edge(zeroth_node, first_node);
+        graph.add_weighted_directed_edge(zeroth_node, second_node, 4);
+        graph.add_directed_edge(first_node, second_node);
+        graph.add_directed_edge(second_node, first_node);
+        graph.add_costed_directed_edge(second_node, second_node, 3, 1);
+
+        assert_eq!(graph.out_degree(zeroth_node), Some(2));
+        assert_eq!(graph.out_degree(first_node), Some(1));
+        assert_eq!(graph.out_degree(second_node), Some(2));
+
+        assert_eq!(graph.in_degree(zeroth_node), Some(0));
+        assert_eq!(graph.in_degree(first_node), Some(2));
+        assert_eq!(graph.in_degree(second_node), Some(3));
+
+        // Edges from or to missing nodes are not added, even if the ID is handed out later
+        graph.add_directed_edge(7, zeroth_node);
+        graph.add_directed_edge(first_node, 3);
+
+        assert_eq!(graph.in_degree(zeroth_node), Some(0));
+        assert_eq!(graph.out_degree(first_node), Some(1));
+        assert_eq!(graph.in_degree(3), None);
+
+        let third_node = graph.add_node(3);
+
+        assert_eq!(graph.in_degree(third_node), Some(0));
+
+        // Undirected edges count once at each end, and self loops once in total
+        let mut graph2 = Graph::new_undirected();
+
+        let node1_id = graph2.add_node(1);
+        let node2_id = graph2.add_node(2);
+
+        graph2.add_undirected_edge(node1_id, node2_id);
+        graph2.add_undirected_edge(node2_id, node2_id);
+
+        assert_eq!(graph2.out_degree(node1_id), Some(1));
+        assert_eq!(graph2.in_degree(node1_id), Some(1));
+        assert_eq!(graph2.out_degree(node2_id), Some(2));
+        assert_eq!(graph2.in_degree(node2_id), Some(2));
+    }
+
+    #[test]
+    fn test_in_edges(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_weighted_directed_edge(zeroth_node, second_node, 3);
+        graph.add_weighted_directed_edge(first_node, second_node, 5);
+        graph.add_weighted_directed_edge(zeroth_node, second_node, 7);
+        graph.add_weighted_directed_edge(second_node, second_node, 9);
+        graph.add_weighted_directed_edge(second_node, zeroth_node, 2);
+
+        let weights: Vec<i64> = graph.in_edges(second_node).unwrap().iter().map(|edge| edge.weight).collect();
+
+        assert_eq!(weights, vec![3, 5, 7, 9]);
+        assert_eq!(graph.predecessors(second_node), Some(vec![zeroth_node, first_node, second_node]));
+        assert_eq!(graph.predecessors(first_node), Some(vec![]));
+        assert!(graph.in_edges(7).is_none());
+
+        // Removing edges and nodes keeps the incoming edges in step
+        graph.remove_edge(zeroth_node, second_node);
+
+        let weights: Vec<i64> = graph.in_edges(second_node).unwrap().iter().map(|edge| edge.weight).collect();
+
+        assert_eq!(weights, vec![5, 7, 9]);
+
+        graph.remove_node(zeroth_node);
+
+        assert_eq!(graph.predecessors(second_node), Some(vec![first_node, second_node]));
+        assert_eq!(graph.in_degree(second_node), Some(2));
+        assert_eq!(graph.out_degree(second_node), Some(1));
+
+        graph.remove_node(second_node);
+
+        assert_eq!(graph.out_degree(first_node), Some(0));
+        assert!(graph.incoming.is_empty());
+
+        // The incoming edges of an undirected graph are its edges seen from the other end
+        let mut graph2 = Graph::new_undirected();
+
+        let node1_id = graph2.add_node(1);
+        let node2_id = graph2.add_node(2);
+        let node3_id = graph2.add_node(3);
+
+        graph2.add_weighted_undirected_edge(node1_id, node2_id, 4);
+        graph2.add_weighted_undirected_edge(node3_id, node1_id, 6);
+
+        let ends: Vec<(u32, u32)> = graph2.in_edges(node1_id).unwrap().iter().map(|edge| (edge.from, edge.to)).collect();
+
+        assert_eq!(ends, vec![(node2_id, node1_id), (node3_id, node1_id)]);
+        assert_eq!(graph2.predecessors(node1_id), Some(vec![node2_id, node3_id]));
+
+        graph2.remove_edge(node1_id, node3_id);
+
+        assert_eq!(graph2.predecessors(node1_id), Some(vec![node2_id]));
+        assert_eq!(graph2.predecessors(node3_id), Some(vec![]));
+    }
+
+    #[test]
+    fn test_read_api(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node('A');
+        let first_node = graph.add_node('B');
+        let second_node = graph.add_node('C');
+
+        graph.add_costed_directed_edge(zeroth_node, first_node, 4, 2);
+        graph.add_weighted_directed_edge(zeroth_node, second_node, 6);
+        graph.add_directed_edge(zeroth_node, first_node);
+
+        assert_eq!(graph.node_ids(), vec![zeroth_node, first_node, second_node]);
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph[second_node], 'C');
+
+        assert_eq!(graph.neighbors(zeroth_node).collect::<Vec<u32>>(), vec![first_node, second_node, first_node]);
+        assert_eq!(graph.neighbors(first_node).count(), 0);
+        assert_eq!(graph.neighbors(7).count(), 0);
+
+        let edges: Vec<(u32, u32, i64, Option<i64>)> = graph.edges(zeroth_node).map(|edge| (edge.from(), edge.to(), *edge.weight(), edge.cost().cloned())).collect();
+
+        assert_eq!(edges, vec![(zeroth_node, first_node, 4, Some(2)), (zeroth_node, second_node, 6, None), (zeroth_node, first_node, 1, None)]);
+
+        let node = graph.get_node(zeroth_node).unwrap();
+
+        assert_eq!(*node.data(), 'A');
+        assert_eq!(node.edges().len(), 3);
+        assert_eq!(node.edges()[1].id(), graph.edges(zeroth_node).nth(1).unwrap().id());
+
+        *graph.get_node_mut(first_node).unwrap().data_mut() = 'D';
+
+        assert_eq!(graph[first_node], 'D');
+
+        graph.remove_node(first_node);
+
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!(graph.edge_count(), 1);
+
+        // Undirected edges count once, but are seen from both ends
+        let mut graph2 = Graph::new_undirected();
+
+        let node1_id = graph2.add_node(1);
+        let node2_id = graph2.add_node(2);
+
+        graph2.add_undirected_edge(node1_id, node2_id);
+        graph2.add_undirected_edge(node1_id, node1_id);
+
+        assert_eq!(graph2.edge_count(), 2);
+        assert_eq!(graph2.neighbors(node1_id).collect::<Vec<u32>>(), vec![node2_id, node1_id]);
+        assert_eq!(graph2.neighbors(node2_id).collect::<Vec<u32>>(), vec![node1_id]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_missing_node(){
+        use graph::Graph;
+
+        let graph: Graph<u32> = Graph::new();
+
+        let _ = graph[0];
+    }
+
+    #[test]
+    fn test_remove_node(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_directed_edge(zeroth_node, first_node);
+        graph.add_directed_edge(first_node, zeroth_node);
+        graph.add_directed_edge(first_node, second_node);
+        graph.add_directed_edge(second_node, first_node);
+        graph.add_directed_edge(first_node, first_node);
+
+        assert_eq!(graph.remove_node(first_node), Some(1));
+        assert_eq!(graph.remove_node(first_node), None);
+
+        assert_eq!(graph.nodes.len(), 2);
+        assert!(graph.nodes[&zeroth_node].edges.is_empty());
+        assert!(graph.nodes[&second_node].edges.is_empty());
+        assert_eq!(graph.in_degree(zeroth_node), Some(0));
+        assert_eq!(graph.in_degree(second_node), Some(0));
+
+        // Removed IDs are not reused, so old IDs can not point at new nodes
+        let third_node = graph.add_node(3);
+
+        assert_eq!(third_node, 3);
+        assert!(graph.get_node(first_node).is_none());
+        assert_eq!(graph.nodes[&zeroth_node].data, 0);
+        assert_eq!(graph.add_node(4), 4);
+    }
+
+    #[test]
+    fn test_remove_edge(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+
+        graph.add_weighted_directed_edge(zeroth_node, first_node, 4);
+        graph.add_weighted_directed_edge(zeroth_node, first_node, 9);
+        graph.add_directed_edge(zeroth_node, first_node);
+        graph.add_directed_edge(first_node, zeroth_node);
+
+        assert_eq!(graph.in_degree(first_node), Some(3));
+
+        assert_eq!(graph.remove_edge(zeroth_node, first_node), Some(4));
+        assert_eq!(graph.nodes[&zeroth_node].edges[0].weight, 9);
+        assert_eq!(graph.in_degree(first_node), Some(2));
+
+        assert_eq!(graph.remove_edge(first_node, zeroth_node), Some(1));
+        assert_eq!(graph.remove_edge(first_node, zeroth_node), None);
+        assert_eq!(graph.remove_edge(7, zeroth_node), None);
+        assert_eq!(graph.in_degree(zeroth_node), Some(0));
+
+        // Removing an undirected edge from either end removes it from both
+        let mut graph2 = Graph::new_undirected();
+
+        let node1_id = graph2.add_node(1);
+        let node2_id = graph2.add_node(2);
+
+        graph2.add_weighted_undirected_edge(node1_id, node2_id, 4);
+        graph2.add_weighted_undirected_edge(node1_id, node2_id, 9);
+
+        assert_eq!(graph2.remove_edge(node2_id, node1_id), Some(4));
+        assert_eq!(graph2.nodes[&node1_id].edges.len(), 1);
+        assert_eq!(graph2.nodes[&node1_id].edges[0].weight, 9);
+        assert_eq!(graph2.nodes[&node2_id].edges[0].weight, 9);
+        assert_eq!(graph2.out_degree(node1_id), Some(1));
+        assert_eq!(graph2.in_degree(node1_id), Some(1));
+    }
+
+    #[test]
+    fn test_try_add_edges(){
+        use graph::{Graph, GraphError};
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        let first_edge = graph.try_add_directed_edge(zeroth_node, first_node).unwrap();
+        let middle_edge = graph.try_add_weighted_directed_edge(second_node, first_node, 5).unwrap();
+        let last_edge = graph.try_add_costed_directed_edge(second_node, zeroth_node, 4, 2).unwrap();
+
+        assert!(first_edge < middle_edge && middle_edge < last_edge);
+        assert_eq!(graph.nodes[&zeroth_node].edges[0].id(), first_edge);
+        assert_eq!(graph.nodes[&second_node].edges[0].id(), middle_edge);
+        assert_eq!(graph.nodes[&second_node].edges[1].cost, Some(2));
+        assert_eq!(graph.in_degree(first_node), Some(2));
+
+        assert_eq!(graph.try_add_directed_edge(7, zeroth_node), Err(GraphError::MissingNode(7)));
+        assert_eq!(graph.try_add_weighted_directed_edge(zeroth_node, 7, 1), Err(GraphError::MissingNode(7)));
+        assert_eq!(graph.try_add_directed_edge(first_node, first_node), Err(GraphError::SelfLoopNotAllowed(first_node)));
+        assert_eq!(graph.try_add_directed_edge(second_node, first_node), Err(GraphError::DuplicateEdge{from: second_node, to: first_node}));
+
+        // Removing an edge makes room for a new one with a new ID
+        graph.remove_edge(zeroth_node, first_node);
+        let replacement = graph.try_add_directed_edge(zeroth_node, first_node).unwrap();
+
+        assert!(replacement > last_edge);
+
+        // An undirected edge has one ID and is a duplicate from either end
+        let mut graph2 = Graph::new_undirected();
+
+        let node1_id = graph2.add_node(1);
+        let node2_id = graph2.add_node(2);
+
+        let edge = graph2.try_add_weighted_undirected_edge(node1_id, node2_id, 5).unwrap();
+
+        assert_eq!(graph2.nodes[&node1_id].edges[0].id(), edge);
+        assert_eq!(graph2.nodes[&node2_id].edges[0].id(), edge);
+        assert_eq!(graph2.try_add_undirected_edge(node2_id, node2_id), Err(GraphError::SelfLoopNotAllowed(node2_id)));
+        assert_eq!(graph2.try_add_undirected_edge(node2_id, node1_id), Err(GraphError::DuplicateEdge{from: node2_id, to: node1_id}));
+        assert_eq!(graph2.out_degree(node1_id), Some(1));
+    }
+}
@@ -0,0 +1,547 @@
+use std::collections::HashMap;
+
+use graph::{Adjacency, Directed, Direction, Graph, Weight, Weighted};
+use graph::shortest_path::{bellman_ford_from, NegativeCycle};
+
+#[derive(Debug, Clone, PartialEq)]
+/// The result of an all pairs shortest path search
+pub struct AllPairsShortestPaths<E = i64>{
+    /// The IDs of the nodes in the graph, in the order they index the matrices
+    pub nodes: Vec<u32>,
+    /// `distances[i][j]` is the length of the shortest path from `nodes[i]` to `nodes[j]`,
+    /// or `None` if there is no path
+    pub distances: Vec<Vec<Option<E>>>,
+    /// `next_hops[i][j]` is the ID of the node after `nodes[i]` on the shortest path from
+    /// `nodes[i]` to `nodes[j]`, or `None` if there is no path
+    pub next_hops: Vec<Vec<Option<u32>>>,
+    indices: HashMap<u32, usize>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Which nodes can reach which other nodes, found by `transitive_closure`
+pub struct TransitiveClosure{
+    /// The IDs of the nodes in the graph, in the order they index the matrix
+    pub nodes: Vec<u32>,
+    /// `reachable[i][j]` is true if there is a path of at least one edge from `nodes[i]` to
+    /// `nodes[j]`, so a node only reaches itself if it is on a cycle
+    pub reachable: Vec<Vec<bool>>,
+    indices: HashMap<u32, usize>
+}
+
+impl<E: Copy> AllPairsShortestPaths<E>{
+
+    fn new(nodes: Vec<u32>) -> Self{
+        let indices = nodes.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+        let distances = vec![vec![None; nodes.len()]; nodes.len()];
+        let next_hops = vec![vec![None; nodes.len()]; nodes.len()];
+
+        AllPairsShortestPaths{nodes, distances, next_hops, indices}
+    }
+
+    /// Returns the length of the shortest path between two nodes, or `None` if there is no path
+    /// # Arguments
+    ///
+    /// `from` - The ID of the node the path starts at
+    /// `to` - The ID of the node the path ends at
+    pub fn distance(&self, from: u32, to: u32) -> Option<E>{
+        let from = *self.indices.get(&from)?;
+        let to = *self.indices.get(&to)?;
+
+        self.distances[from][to]
+    }
+
+    /// Returns the IDs of the nodes on the shortest path between two nodes, including both
+    /// ends, or `None` if there is no path
+    /// # Arguments
+    ///
+    /// `from` - The ID of the node the path starts at
+    /// `to` - The ID of the node the path ends at
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_weighted_directed_edge(node1, node2, 1);
+    /// graph.add_weighted_directed_edge(node2, node3, 1);
+    /// graph.add_weighted_directed_edge(node1, node3, 5);
+    ///
+    /// let paths = graph.floyd_warshall().unwrap();
+    ///
+    /// assert_eq!(paths.path(node1, node3), Some(vec![node1, node2, node3]));
+    /// assert_eq!(paths.path(node3, node1), None);
+    /// ```
+    pub fn path(&self, from: u32, to: u32) -> Option<Vec<u32>>{
+        let to_index = *self.indices.get(&to)?;
+        self.distance(from, to)?;
+
+        let mut path = vec![from];
+        let mut current = from;
+        while current != to{
+            current = self.next_hops[self.indices[&current]][to_index]?;
+            path.push(current);
+        }
+
+        Some(path)
+    }
+}
+
+impl TransitiveClosure{
+
+    /// Returns true if there is a path of at least one edge from one node to another
+    /// # Arguments
+    ///
+    /// `from` - The ID of the node the path starts at
+    /// `to` - The ID of the node the path ends at
+    pub fn reaches(&self, from: u32, to: u32) -> bool{
+        match (self.indices.get(&from), self.indices.get(&to)){
+            (Some(&from), Some(&to)) => self.reachable[from][to],
+            _ => false
+        }
+    }
+}
+
+impl<T, E: Weighted> Graph<T, E, Directed>{
+
+    /// Find the shortest paths between every pair of nodes using the Floyd-Warshall algorithm.
+    /// See `all_pairs::floyd_warshall`.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_weighted_directed_edge(node1, node2, 4);
+    /// graph.add_weighted_directed_edge(node2, node3, -2);
+    /// graph.add_weighted_directed_edge(node3, node1, 3);
+    ///
+    /// let paths = graph.floyd_warshall().unwrap();
+    ///
+    /// assert_eq!(paths.distance(node1, node3), Some(2));
+    /// assert_eq!(paths.distance(node2, node1), Some(1));
+    /// ```
+    pub fn floyd_warshall(&self) -> Result<AllPairsShortestPaths<E::Weight>, NegativeCycle>{
+        floyd_warshall(self)
+    }
+
+    /// Find the shortest paths between every pair of nodes using Johnson's algorithm.
+    /// Bellman-Ford is used to reweight the edges so they are non negative, then Dijkstra's
+    /// algorithm is run from every node.  Runs in O(nm log n) time, which suits sparse graphs.
+    /// A `NegativeCycle` error is returned if the graph contains a negative weight cycle.  Use
+    /// `dijkstra` from each node for undirected graphs, which can not have negative weights.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_weighted_directed_edge(node1, node2, 4);
+    /// graph.add_weighted_directed_edge(node2, node3, -2);
+    /// graph.add_weighted_directed_edge(node3, node1, 3);
+    ///
+    /// let paths = graph.johnson().unwrap();
+    ///
+    /// assert_eq!(paths.distance(node1, node3), Some(2));
+    /// assert_eq!(paths.path(node2, node1), Some(vec![node2, node3, node1]));
+    /// ```
+    pub fn johnson(&self) -> Result<AllPairsShortestPaths<E::Weight>, NegativeCycle>{
+        let node_ids = self.sorted_node_ids();
+
+        // The potential of each node is its distance from an extra node joined to every node
+        let (potentials, _) = bellman_ford_from(self, &node_ids)?;
+
+        let mut paths = AllPairsShortestPaths::new(node_ids);
+
+        for i in 0..paths.nodes.len(){
+            let source = paths.nodes[i];
+
+            // Reweighting keeps shortest paths the same but makes every edge non negative.
+            // Floating point rounding can leave a tiny negative weight, which is taken as zero.
+            let reweighted = self.dijkstra_by(source, |edge| {
+                let weight = edge.weight.weight() + potentials[&edge.from] - potentials[&edge.to];
+                if weight < E::Weight::zero() { E::Weight::zero() } else { weight }
+            });
+
+            let mut first_hops = HashMap::new();
+            first_hops.insert(source, source);
+
+            for (&target, &distance) in &reweighted.distances{
+                let j = paths.indices[&target];
+
+                paths.distances[i][j] = Some(distance - potentials[&source] + potentials[&target]);
+                paths.next_hops[i][j] = Some(first_hop(&reweighted.predecessors, &mut first_hops, source, target));
+            }
+        }
+
+        Ok(paths)
+    }
+}
+
+impl<T, E, D: Direction> Graph<T, E, D>{
+
+    /// Find which nodes can reach which other nodes with Warshall's algorithm.  See
+    /// `all_pairs::transitive_closure`.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node1, node2);
+    /// graph.add_directed_edge(node2, node3);
+    ///
+    /// let closure = graph.transitive_closure();
+    ///
+    /// assert!(closure.reaches(node1, node3));
+    /// assert!(!closure.reaches(node3, node1));
+    /// assert!(!closure.reaches(node1, node1));
+    /// ```
+    pub fn transitive_closure(&self) -> TransitiveClosure{
+        transitive_closure(self)
+    }
+}
+
+/// Find the shortest paths between every pair of nodes of any directed graph backend using the
+/// Floyd-Warshall algorithm.  Runs in O(n^3) time.  Edges may have negative weights, but a
+/// `NegativeCycle` error is returned if the graph contains a negative weight cycle.  Undirected
+/// graphs are not supported, as an undirected edge with a negative weight is a negative cycle
+/// on its own.
+///
+/// # Example
+/// ```
+/// # use kyles_algorithm_crate::graph::all_pairs::floyd_warshall;
+/// # use kyles_algorithm_crate::graph::matrix::MatrixGraph;
+/// // Create a graph
+/// let mut graph = MatrixGraph::new();
+///
+/// let node1 = graph.add_node(1);
+/// let node2 = graph.add_node(2);
+///
+/// graph.add_weighted_directed_edge(node1, node2, 4);
+///
+/// let paths = floyd_warshall(&graph).unwrap();
+///
+/// assert_eq!(paths.distance(node1, node2), Some(4));
+/// ```
+pub fn floyd_warshall<G>(graph: &G) -> Result<AllPairsShortestPaths<<G::Payload as Weighted>::Weight>, NegativeCycle>
+    where G: Adjacency<Direction = Directed>, G::Payload: Weighted {
+    let mut paths = AllPairsShortestPaths::new(graph.node_ids());
+    let n = paths.nodes.len();
+
+    for i in 0..n{
+        paths.distances[i][i] = Some(Weight::zero());
+        paths.next_hops[i][i] = Some(paths.nodes[i]);
+    }
+
+    // Start with the direct edges, keeping the lightest of any parallel edges
+    for i in 0..n{
+        let (distances, next_hops, indices) = (&mut paths.distances[i], &mut paths.next_hops[i], &paths.indices);
+
+        graph.for_each_edge(paths.nodes[i], |to, payload| {
+            let j = indices[&to];
+            let weight = payload.weight();
+
+            if distances[j].is_none_or(|distance| weight < distance){
+                distances[j] = Some(weight);
+                next_hops[j] = Some(to);
+            }
+        });
+    }
+
+    // Allow paths to go through each node in turn
+    for k in 0..n{
+        for i in 0..n{
+            let to_k = match paths.distances[i][k]{
+                Some(distance) => distance,
+                None => continue
+            };
+
+            for j in 0..n{
+                let through_k = match paths.distances[k][j]{
+                    Some(distance) => to_k + distance,
+                    None => continue
+                };
+
+                if paths.distances[i][j].is_none_or(|distance| through_k < distance){
+                    // A node with a negative path back to itself lies on a negative cycle.  Stop
+                    // now, as going round the cycle again keeps lowering the distances until
+                    // they overflow.
+                    if i == j && through_k < Weight::zero(){
+                        // The distance is the length of a closed walk from the node back to
+                        // itself.  A negative closed walk splits into simple cycles, at least
+                        // one of them negative and all reachable from the node, so Bellman-Ford
+                        // from the node must report a cycle, the same one Graph::bellman_ford
+                        // would.
+                        match bellman_ford_from(graph, &[paths.nodes[i]]){
+                            Err(cycle) => return Err(cycle),
+                            Ok(_) => unreachable!("a negative closed walk was found, so Bellman-Ford must find a negative cycle")
+                        }
+                    }
+
+                    paths.distances[i][j] = Some(through_k);
+                    paths.next_hops[i][j] = paths.next_hops[i][k];
+                }
+            }
+        }
+    }
+
+    Ok(paths)
+}
+
+/// Find which nodes of any graph backend can reach which other nodes with Warshall's
+/// algorithm.  Runs in O(n^3) time.
+///
+/// # Example
+/// ```
+/// # use kyles_algorithm_crate::graph::all_pairs::transitive_closure;
+/// # use kyles_algorithm_crate::graph::matrix::MatrixGraph;
+/// // Create a graph
+/// let mut graph = MatrixGraph::new();
+///
+/// let node1 = graph.add_node(1);
+/// let node2 = graph.add_node(2);
+///
+/// graph.add_directed_edge(node1, node2);
+///
+/// let closure = transitive_closure(&graph);
+///
+/// assert!(closure.reaches(node1, node2));
+/// assert!(!closure.reaches(node2, node1));
+/// ```
+pub fn transitive_closure<G: Adjacency>(graph: &G) -> TransitiveClosure{
+    let nodes = graph.node_ids();
+    let indices: HashMap<u32, usize> = nodes.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+    let n = nodes.len();
+
+    let mut reachable = vec![vec![false; n]; n];
+    for i in 0..n{
+        graph.for_each_edge(nodes[i], |to, _| reachable[i][indices[&to]] = true);
+    }
+
+    // Anything `k` reaches can be reached by every node that reaches `k`
+    for k in 0..n{
+        let through_k = reachable[k].clone();
+
+        for row in reachable.iter_mut(){
+            if row[k]{
+                for (reaches, &k_reaches) in row.iter_mut().zip(&through_k){
+                    *reaches |= k_reaches;
+                }
+            }
+        }
+    }
+
+    TransitiveClosure{nodes, reachable, indices}
+}
+
+/// Returns the node after `source` on the path to `target` in a shortest path tree, remembering
+/// the answer for every node passed on the way
+fn first_hop(predecessors: &HashMap<u32, u32>, first_hops: &mut HashMap<u32, u32>, source: u32, target: u32) -> u32{
+    let mut unknown = Vec::new();
+    let mut current = target;
+
+    // Walk back until reaching a node whose first hop is known or a child of the source
+    let hop = loop{
+        if let Some(&hop) = first_hops.get(&current){
+            break hop;
+        }
+
+        let predecessor = predecessors[&current];
+        unknown.push(current);
+
+        if predecessor == source{
+            break current;
+        }
+        current = predecessor;
+    };
+
+    for node_id in unknown{
+        first_hops.insert(node_id, hop);
+    }
+
+    hop
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_floyd_warshall(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_weighted_directed_edge(zeroth_node, second_node, -2);
+        graph.add_weighted_directed_edge(second_node, third_node, 2);
+        graph.add_weighted_directed_edge(third_node, first_node, -1);
+        graph.add_weighted_directed_edge(first_node, zeroth_node, 4);
+        graph.add_weighted_directed_edge(first_node, second_node, 3);
+
+        let paths = graph.floyd_warshall().unwrap();
+
+        assert_eq!(paths.distance(zeroth_node, first_node), Some(-1));
+        assert_eq!(paths.distance(first_node, third_node), Some(4));
+        assert_eq!(paths.distance(third_node, zeroth_node), Some(3));
+        assert_eq!(paths.distance(second_node, second_node), Some(0));
+
+        assert_eq!(paths.path(zeroth_node, first_node), Some(vec![zeroth_node, second_node, third_node, first_node]));
+        assert_eq!(paths.path(third_node, second_node), Some(vec![third_node, first_node, zeroth_node, second_node]));
+        assert_eq!(paths.path(second_node, second_node), Some(vec![second_node]));
+
+        // Unreachable and missing nodes have no paths
+        let fourth_node = graph.add_node(4);
+        let paths = graph.floyd_warshall().unwrap();
+
+        assert_eq!(paths.distance(zeroth_node, fourth_node), None);
+        assert_eq!(paths.path(fourth_node, zeroth_node), None);
+        assert_eq!(paths.path(zeroth_node, 7), None);
+    }
+
+    #[test]
+    fn test_floyd_warshall_negative_cycle(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_weighted_directed_edge(zeroth_node, first_node, 1);
+        graph.add_weighted_directed_edge(first_node, second_node, -3);
+        graph.add_weighted_directed_edge(second_node, first_node, 2);
+
+        let error = graph.floyd_warshall().unwrap_err();
+
+        assert_eq!(error.cycle, vec![first_node, second_node]);
+
+        // Every later node is a way round the cycle again, which would overflow if the search
+        // carried on after finding it
+        let mut graph2 = Graph::new();
+
+        let zeroth_node = graph2.add_node(0);
+        let first_node = graph2.add_node(1);
+
+        graph2.add_weighted_directed_edge(zeroth_node, first_node, -1_000_000);
+        graph2.add_weighted_directed_edge(first_node, zeroth_node, 1);
+
+        for i in 2..100{
+            let node = graph2.add_node(i);
+
+            graph2.add_weighted_directed_edge(zeroth_node, node, 0);
+            graph2.add_weighted_directed_edge(node, zeroth_node, 0);
+        }
+
+        let error = graph2.floyd_warshall().unwrap_err();
+
+        assert_eq!(error.cycle, vec![zeroth_node, first_node]);
+    }
+
+    #[test]
+    fn test_johnson(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+
+        graph.add_weighted_directed_edge(zeroth_node, second_node, -2);
+        graph.add_weighted_directed_edge(second_node, third_node, 2);
+        graph.add_weighted_directed_edge(third_node, first_node, -1);
+        graph.add_weighted_directed_edge(first_node, zeroth_node, 4);
+        graph.add_weighted_directed_edge(first_node, second_node, 3);
+
+        let paths = graph.johnson().unwrap();
+
+        assert_eq!(paths.distance(zeroth_node, first_node), Some(-1));
+        assert_eq!(paths.distance(third_node, zeroth_node), Some(3));
+        assert_eq!(paths.distance(zeroth_node, fourth_node), None);
+
+        assert_eq!(paths.path(zeroth_node, first_node), Some(vec![zeroth_node, second_node, third_node, first_node]));
+        assert_eq!(paths.path(third_node, second_node), Some(vec![third_node, first_node, zeroth_node, second_node]));
+
+        // Both algorithms agree on every pair
+        assert_eq!(paths, graph.floyd_warshall().unwrap());
+    }
+
+    #[test]
+    fn test_johnson_negative_cycle(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_weighted_directed_edge(zeroth_node, first_node, 1);
+        graph.add_weighted_directed_edge(first_node, second_node, -3);
+        graph.add_weighted_directed_edge(second_node, first_node, 2);
+
+        let error = graph.johnson().unwrap_err();
+
+        assert_eq!(error.cycle, vec![first_node, second_node]);
+    }
+
+    #[test]
+    fn test_float_weights(){
+        use graph::Graph;
+
+        let mut graph: Graph<u32, f64> = Graph::new_weighted();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_weighted_directed_edge(zeroth_node, second_node, -0.5);
+        graph.add_weighted_directed_edge(second_node, third_node, 0.75);
+        graph.add_weighted_directed_edge(third_node, first_node, -0.25);
+        graph.add_weighted_directed_edge(first_node, zeroth_node, 1.5);
+        graph.add_weighted_directed_edge(first_node, second_node, 1.25);
+
+        let paths = graph.floyd_warshall().unwrap();
+
+        assert_eq!(paths.distance(zeroth_node, first_node), Some(0.0));
+        assert_eq!(paths.distance(third_node, zeroth_node), Some(1.25));
+        assert_eq!(paths.path(first_node, third_node), Some(vec![first_node, zeroth_node, second_node, third_node]));
+
+        // Both algorithms agree on every pair
+        assert_eq!(paths, graph.johnson().unwrap());
+
+        // A cheaper way back makes a negative cycle
+        graph.add_weighted_directed_edge(first_node, zeroth_node, -0.125);
+
+        assert_eq!(graph.floyd_warshall().unwrap_err().cycle, vec![zeroth_node, second_node, third_node, first_node]);
+        assert_eq!(graph.johnson().unwrap_err().cycle, vec![zeroth_node, second_node, third_node, first_node]);
+    }
+}
@@ -0,0 +1,475 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use graph::{Graph, Undirected, Weight, Weighted};
+use graph::matching::OddCycle;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A perfect matching between the two sides of a bipartite graph
+pub struct Assignment<E = i64>{
+    /// The matched pairs, given as `(left node, right node)` and sorted
+    pub pairs: Vec<(u32, u32)>,
+    /// The sum of the weights of the matched edges
+    pub total_weight: E
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Error returned when a graph is not a complete bipartite graph with two equal sides
+pub enum AssignmentError{
+    /// The graph is not bipartite
+    NotBipartite(OddCycle),
+    /// The two sides have different numbers of nodes, so no perfect matching exists
+    UnequalSides{
+        /// The number of nodes on the left side
+        left: usize,
+        /// The number of nodes on the right side
+        right: usize
+    },
+    /// There is no edge between a node on the left side and a node on the right side
+    MissingEdge{
+        /// The ID of the node on the left side
+        left: u32,
+        /// The ID of the node on the right side
+        right: u32
+    }
+}
+
+impl fmt::Display for AssignmentError{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match *self{
+            AssignmentError::NotBipartite(ref odd_cycle) => write!(f, "{}", odd_cycle),
+            AssignmentError::UnequalSides{left, right} => write!(f, "sides have {} and {} nodes, so no perfect matching exists", left, right),
+            AssignmentError::MissingEdge{left, right} => write!(f, "graph is not complete, no edge between nodes {} and {}", left, right)
+        }
+    }
+}
+
+impl Error for AssignmentError{}
+
+impl<T, E: Weighted> Graph<T, E, Undirected>{
+
+    /// Find a perfect matching of a complete bipartite graph with the smallest total edge
+    /// weight, using the Hungarian algorithm.  The sides are the ones found by `is_bipartite`.
+    /// If there are several edges between two nodes the lightest one is used.  Runs in O(n^3)
+    /// time.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph of workers and the cost of each doing each job
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let alice = graph.add_node("alice");
+    /// let bob = graph.add_node("bob");
+    /// let painting = graph.add_node("painting");
+    /// let plumbing = graph.add_node("plumbing");
+    ///
+    /// graph.add_weighted_undirected_edge(alice, painting, 3);
+    /// graph.add_weighted_undirected_edge(alice, plumbing, 5);
+    /// graph.add_weighted_undirected_edge(bob, painting, 4);
+    /// graph.add_weighted_undirected_edge(bob, plumbing, 8);
+    ///
+    /// let assignment = graph.min_weight_perfect_matching().unwrap();
+    ///
+    /// assert_eq!(assignment.pairs, vec![(alice, plumbing), (bob, painting)]);
+    /// assert_eq!(assignment.total_weight, 9);
+    /// ```
+    pub fn min_weight_perfect_matching(&self) -> Result<Assignment<E::Weight>, AssignmentError>{
+        self.hungarian(false)
+    }
+
+    /// Find a perfect matching of a complete bipartite graph with the largest total edge
+    /// weight, using the Hungarian algorithm.  The sides are the ones found by `is_bipartite`.
+    /// If there are several edges between two nodes the heaviest one is used.  Runs in O(n^3)
+    /// time.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph of workers and the profit of each doing each job
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let alice = graph.add_node("alice");
+    /// let bob = graph.add_node("bob");
+    /// let painting = graph.add_node("painting");
+    /// let plumbing = graph.add_node("plumbing");
+    ///
+    /// graph.add_weighted_undirected_edge(alice, painting, 3);
+    /// graph.add_weighted_undirected_edge(alice, plumbing, 5);
+    /// graph.add_weighted_undirected_edge(bob, painting, 4);
+    /// graph.add_weighted_undirected_edge(bob, plumbing, 8);
+    ///
+    /// let assignment = graph.max_weight_perfect_matching().unwrap();
+    ///
+    /// assert_eq!(assignment.pairs, vec![(alice, painting), (bob, plumbing)]);
+    /// assert_eq!(assignment.total_weight, 11);
+    /// ```
+    pub fn max_weight_perfect_matching(&self) -> Result<Assignment<E::Weight>, AssignmentError>{
+        self.hungarian(true)
+    }
+
+    /// Build the weight matrix between the two sides and solve it, flipping the weights when
+    /// `maximize` is set
+    fn hungarian(&self, maximize: bool) -> Result<Assignment<E::Weight>, AssignmentError>{
+        let sides = self.is_bipartite().map_err(AssignmentError::NotBipartite)?;
+
+        if sides.left.len() != sides.right.len(){
+            return Err(AssignmentError::UnequalSides{left: sides.left.len(), right: sides.right.len()});
+        }
+
+        let left_indices: HashMap<u32, usize> = sides.left.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+        let right_indices: HashMap<u32, usize> = sides.right.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+
+        let mut weights: Vec<Vec<Option<E::Weight>>> = vec![vec![None; sides.right.len()]; sides.left.len()];
+        for node_id in self.sorted_node_ids(){
+            for edge in self.edges(node_id){
+                // Every edge is seen once from its end on the left side
+                let (left, right) = match (left_indices.get(&edge.from), right_indices.get(&edge.to)){
+                    (Some(&left), Some(&right)) => (left, right),
+                    _ => continue
+                };
+
+                let weight = edge.weight.weight();
+                let better = match weights[left][right]{
+                    Some(best) if maximize => weight > best,
+                    Some(best) => weight < best,
+                    None => true
+                };
+
+                if better{
+                    weights[left][right] = Some(weight);
+                }
+            }
+        }
+
+        let mut matrix = Vec::new();
+        for (left, row) in weights.iter().enumerate(){
+            let mut matrix_row = Vec::new();
+            for (right, weight) in row.iter().enumerate(){
+                match *weight{
+                    Some(weight) => matrix_row.push(weight),
+                    None => return Err(AssignmentError::MissingEdge{left: sides.left[left], right: sides.right[right]})
+                }
+            }
+            matrix.push(matrix_row);
+        }
+
+        // Taking each weight away from the largest turns the heaviest matching into the
+        // cheapest one without going below zero, so unsigned weights work too
+        let costs = if maximize{
+            let largest = matrix.iter().flatten().cloned().reduce(|largest, weight| if weight > largest { weight } else { largest });
+            matrix.iter().map(|row| row.iter().map(|&weight| largest.unwrap() - weight).collect()).collect()
+        }else{
+            matrix.clone()
+        };
+
+        let columns = solve_assignment(&costs);
+
+        Ok(Assignment{
+            pairs: columns.iter().enumerate().map(|(row, &column)| (sides.left[row], sides.right[column])).collect(),
+            total_weight: columns.iter().enumerate().fold(E::Weight::zero(), |total, (row, &column)| total + matrix[row][column])
+        })
+    }
+}
+
+/// Solve the assignment problem for a square cost matrix, giving each row the column it is
+/// assigned to so that the total cost is as small as possible.  The result is an `Assignment`
+/// where each pair is `(row, column)`.
+///
+/// # Arguments
+///
+/// `costs` - The cost of assigning each row to each column
+///
+/// # Panics
+///
+/// Panics if the matrix is not square.
+///
+/// # Example
+/// ```
+/// # use kyles_algorithm_crate::graph::assignment::min_cost_assignment;
+/// let costs = vec![
+///     vec![4, 1, 3],
+///     vec![2, 0, 5],
+///     vec![3, 2, 2]
+/// ];
+///
+/// let assignment = min_cost_assignment(&costs);
+///
+/// assert_eq!(assignment.pairs, vec![(0, 1), (1, 0), (2, 2)]);
+/// assert_eq!(assignment.total_weight, 5);
+/// ```
+pub fn min_cost_assignment<W: Weight>(costs: &[Vec<W>]) -> Assignment<W>{
+    matrix_assignment(costs, false)
+}
+
+/// Solve the assignment problem for a square profit matrix, giving each row the column it is
+/// assigned to so that the total profit is as large as possible.  The result is an `Assignment`
+/// where each pair is `(row, column)`.
+///
+/// # Arguments
+///
+/// `profits` - The profit of assigning each row to each column
+///
+/// # Panics
+///
+/// Panics if the matrix is not square.
+///
+/// # Example
+/// ```
+/// # use kyles_algorithm_crate::graph::assignment::max_cost_assignment;
+/// let profits = vec![
+///     vec![4, 1, 3],
+///     vec![2, 0, 5],
+///     vec![3, 2, 2]
+/// ];
+///
+/// let assignment = max_cost_assignment(&profits);
+///
+/// assert_eq!(assignment.pairs, vec![(0, 0), (1, 2), (2, 1)]);
+/// assert_eq!(assignment.total_weight, 11);
+/// ```
+pub fn max_cost_assignment<W: Weight>(profits: &[Vec<W>]) -> Assignment<W>{
+    matrix_assignment(profits, true)
+}
+
+/// Build a complete bipartite graph with the rows on one side and the columns on the other,
+/// and match it
+fn matrix_assignment<W: Weight>(costs: &[Vec<W>], maximize: bool) -> Assignment<W>{
+    let size = costs.len() as u32;
+    assert!(costs.iter().all(|row| row.len() == costs.len()), "cost matrix must be square");
+
+    let mut graph: Graph<u32, W, Undirected> = Graph::new_weighted();
+    for i in 0..size * 2{
+        graph.add_node(i);
+    }
+
+    for (row, row_costs) in costs.iter().enumerate(){
+        for (column, &cost) in row_costs.iter().enumerate(){
+            graph.add_weighted_undirected_edge(row as u32, size + column as u32, cost);
+        }
+    }
+
+    // The rows hold the smallest ID, so they are always the left side
+    let mut assignment = graph.hungarian(maximize).unwrap();
+    for pair in &mut assignment.pairs{
+        pair.1 -= size;
+    }
+
+    assignment
+}
+
+/// The Hungarian algorithm with row and column potentials, adding one row at a time and
+/// growing a tree of tight edges until it reaches a free column.  Returns the column assigned
+/// to each row.
+fn solve_assignment<W: Weight>(costs: &[Vec<W>]) -> Vec<usize>{
+    let size = costs.len();
+
+    // Index 0 is a placeholder column that the row being added starts from.  Column potentials
+    // only go down, so they are kept negated, which keeps every amount at or above zero when
+    // the costs are.
+    let mut row_potentials = vec![W::zero(); size + 1];
+    let mut column_drops = vec![W::zero(); size + 1];
+    let mut column_rows = vec![0; size + 1];
+    let mut way = vec![0; size + 1];
+
+    for row in 1..size + 1{
+        column_rows[0] = row;
+        let mut column = 0;
+        let mut slack: Vec<Option<W>> = vec![None; size + 1];
+        let mut used = vec![false; size + 1];
+
+        loop{
+            used[column] = true;
+            let current_row = column_rows[column];
+            let mut delta = None;
+            let mut next_column = 0;
+
+            for j in 1..size + 1{
+                if used[j]{
+                    continue;
+                }
+
+                let reduced = costs[current_row - 1][j - 1] + column_drops[j] - row_potentials[current_row];
+                if slack[j].is_none_or(|slack| reduced < slack){
+                    slack[j] = Some(reduced);
+                    way[j] = column;
+                }
+                if delta.is_none_or(|delta| slack[j].unwrap() < delta){
+                    delta = slack[j];
+                    next_column = j;
+                }
+            }
+
+            // Shift the potentials so the cheapest edge leaving the tree becomes tight
+            let delta = delta.unwrap();
+            for j in 0..size + 1{
+                if used[j]{
+                    row_potentials[column_rows[j]] = row_potentials[column_rows[j]] + delta;
+                    column_drops[j] = column_drops[j] + delta;
+                }else{
+                    slack[j] = slack[j].map(|slack| slack - delta);
+                }
+            }
+
+            column = next_column;
+            if column_rows[column] == 0{
+                break;
+            }
+        }
+
+        // Flip the alternating path back to the placeholder column
+        while column != 0{
+            let previous = way[column];
+            column_rows[column] = column_rows[previous];
+            column = previous;
+        }
+    }
+
+    let mut columns = vec![0; size];
+    for j in 1..size + 1{
+        columns[column_rows[j] - 1] = j - 1;
+    }
+
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_min_weight_perfect_matching(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        // Parallel edges use the lightest
+        graph.add_weighted_undirected_edge(zeroth_node, second_node, 7);
+        graph.add_weighted_undirected_edge(third_node, zeroth_node, 2);
+        graph.add_weighted_undirected_edge(first_node, second_node, 3);
+        graph.add_weighted_undirected_edge(first_node, third_node, 9);
+        graph.add_weighted_undirected_edge(first_node, third_node, 1);
+
+        let assignment = graph.min_weight_perfect_matching().unwrap();
+
+        assert_eq!(assignment.pairs, vec![(zeroth_node, third_node), (first_node, second_node)]);
+        assert_eq!(assignment.total_weight, 5);
+
+        let assignment = graph.max_weight_perfect_matching().unwrap();
+
+        assert_eq!(assignment.pairs, vec![(zeroth_node, second_node), (first_node, third_node)]);
+        assert_eq!(assignment.total_weight, 16);
+    }
+
+    #[test]
+    fn test_perfect_matching_errors(){
+        use graph::{Graph, Undirected};
+        use graph::assignment::AssignmentError;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_undirected_edge(zeroth_node, first_node);
+        graph.add_undirected_edge(zeroth_node, second_node);
+
+        assert_eq!(graph.min_weight_perfect_matching(), Err(AssignmentError::UnequalSides{left: 1, right: 2}));
+
+        let third_node = graph.add_node(3);
+        graph.add_undirected_edge(third_node, first_node);
+
+        assert_eq!(graph.min_weight_perfect_matching(), Err(AssignmentError::MissingEdge{left: third_node, right: second_node}));
+
+        graph.add_undirected_edge(first_node, second_node);
+
+        match graph.max_weight_perfect_matching(){
+            Err(AssignmentError::NotBipartite(odd_cycle)) => assert_eq!(odd_cycle.cycle.len(), 3),
+            other => panic!("expected an odd cycle, got {:?}", other)
+        }
+
+        // An empty graph has an empty matching
+        let graph2: Graph<u32, i64, Undirected> = Graph::new_undirected();
+
+        assert_eq!(graph2.min_weight_perfect_matching().unwrap().pairs, vec![]);
+    }
+
+    #[test]
+    fn test_cost_assignment_brute_force(){
+        use graph::assignment::{max_cost_assignment, min_cost_assignment};
+        use graph::test_util::Lcg;
+
+        let mut random = Lcg::new(7);
+
+        for size in 1..7{
+            let costs: Vec<Vec<i64>> = (0..size).map(|_| (0..size).map(|_| random.in_range(-10..40)).collect()).collect();
+
+            // Try every permutation of the columns
+            let mut best_min = i64::MAX;
+            let mut best_max = i64::MIN;
+            let mut permutation: Vec<usize> = (0..size).collect();
+            loop{
+                let total: i64 = permutation.iter().enumerate().map(|(row, &column)| costs[row][column]).sum();
+                best_min = best_min.min(total);
+                best_max = best_max.max(total);
+
+                // Step to the next permutation in lexicographic order
+                let pivot = match (0..size - 1).rev().find(|&i| permutation[i] < permutation[i + 1]){
+                    Some(pivot) => pivot,
+                    None => break
+                };
+                let swap = (pivot + 1..size).rev().find(|&i| permutation[i] > permutation[pivot]).unwrap();
+                permutation.swap(pivot, swap);
+                permutation[pivot + 1..].reverse();
+            }
+
+            let assignment = min_cost_assignment(&costs);
+            assert_eq!(assignment.total_weight, best_min);
+
+            let total: i64 = assignment.pairs.iter().map(|&(row, column)| costs[row as usize][column as usize]).sum();
+            assert_eq!(total, best_min);
+
+            let mut columns: Vec<u32> = assignment.pairs.iter().map(|&(_, column)| column).collect();
+            columns.sort();
+            assert_eq!(columns, (0..size as u32).collect::<Vec<u32>>());
+
+            assert_eq!(max_cost_assignment(&costs).total_weight, best_max);
+        }
+    }
+
+    #[test]
+    fn test_unsigned_and_float_weights(){
+        use graph::{Graph, Undirected};
+        use graph::assignment::{max_cost_assignment, min_cost_assignment};
+
+        // Maximising must not take unsigned weights below zero
+        let profits: Vec<Vec<u32>> = vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]];
+
+        assert_eq!(max_cost_assignment(&profits).total_weight, 11);
+        assert_eq!(min_cost_assignment(&profits).total_weight, 5);
+
+        let mut graph: Graph<u32, f64, Undirected> = Graph::new_weighted();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_weighted_undirected_edge(zeroth_node, second_node, 1.5);
+        graph.add_weighted_undirected_edge(zeroth_node, third_node, 0.5);
+        graph.add_weighted_undirected_edge(first_node, second_node, 0.25);
+        graph.add_weighted_undirected_edge(first_node, third_node, 2.0);
+
+        let assignment = graph.min_weight_perfect_matching().unwrap();
+
+        assert_eq!(assignment.pairs, vec![(zeroth_node, third_node), (first_node, second_node)]);
+        assert_eq!(assignment.total_weight, 0.75);
+        assert_eq!(graph.max_weight_perfect_matching().unwrap().total_weight, 3.5);
+    }
+}
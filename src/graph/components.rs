@@ -0,0 +1,360 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+use graph::{Directed, Graph};
+
+impl<T, E> Graph<T, E, Directed>{
+
+    /// Find the strongly connected components of the graph using Tarjan's algorithm.
+    /// Returns the component ID of every node.  Components are numbered in topological order,
+    /// so every edge between two components goes from a lower ID to a higher one.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node1, node2);
+    /// graph.add_directed_edge(node2, node1);
+    /// graph.add_directed_edge(node2, node3);
+    ///
+    /// let components = graph.tarjan_scc();
+    ///
+    /// assert_eq!(components[&node1], components[&node2]);
+    /// assert!(components[&node1] < components[&node3]);
+    /// ```
+    pub fn tarjan_scc(&self) -> HashMap<u32, u32>{
+        let mut indices: HashMap<u32, usize> = HashMap::new();
+        let mut low_links: HashMap<u32, usize> = HashMap::new();
+        let mut on_stack = HashSet::new();
+        let mut stack = Vec::new();
+        let mut found = Vec::new();
+
+        for root in self.sorted_node_ids(){
+            if indices.contains_key(&root){
+                continue;
+            }
+
+            // Each entry is a node being visited and the index of its next edge to follow
+            let mut calls = vec![(root, 0)];
+            indices.insert(root, indices.len());
+            low_links.insert(root, indices[&root]);
+            stack.push(root);
+            on_stack.insert(root);
+
+            while let Some(&mut (node_id, ref mut next_edge)) = calls.last_mut(){
+                let edges = self.edges(node_id).as_slice();
+
+                if *next_edge < edges.len(){
+                    let to = edges[*next_edge].to;
+                    *next_edge += 1;
+
+                    if !self.contains_node(to){
+                        continue;
+                    }
+
+                    if !indices.contains_key(&to){
+                        indices.insert(to, indices.len());
+                        low_links.insert(to, indices[&to]);
+                        stack.push(to);
+                        on_stack.insert(to);
+                        calls.push((to, 0));
+                    }else if on_stack.contains(&to){
+                        let low_link = low_links[&node_id].min(indices[&to]);
+                        low_links.insert(node_id, low_link);
+                    }
+                    continue;
+                }
+
+                calls.pop();
+
+                // A node that can not reach anything earlier on the stack is the root of a component
+                if low_links[&node_id] == indices[&node_id]{
+                    let mut component = Vec::new();
+                    loop{
+                        let member = stack.pop().unwrap();
+                        on_stack.remove(&member);
+                        component.push(member);
+
+                        if member == node_id{
+                            break;
+                        }
+                    }
+                    found.push(component);
+                }
+
+                if let Some(&(parent, _)) = calls.last(){
+                    let low_link = low_links[&parent].min(low_links[&node_id]);
+                    low_links.insert(parent, low_link);
+                }
+            }
+        }
+
+        // Tarjan's algorithm finds components in reverse topological order
+        let mut components = HashMap::new();
+        for (i, component) in found.iter().rev().enumerate(){
+            for &member in component{
+                components.insert(member, i as u32);
+            }
+        }
+
+        components
+    }
+
+    /// Find the strongly connected components of the graph using Kosaraju's algorithm.
+    /// Returns the component ID of every node.  Components are numbered in topological order,
+    /// so every edge between two components goes from a lower ID to a higher one.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node1, node2);
+    /// graph.add_directed_edge(node2, node1);
+    /// graph.add_directed_edge(node3, node2);
+    ///
+    /// let components = graph.kosaraju_scc();
+    ///
+    /// assert_eq!(components[&node1], components[&node2]);
+    /// assert!(components[&node3] < components[&node1]);
+    /// ```
+    pub fn kosaraju_scc(&self) -> HashMap<u32, u32>{
+        let node_ids = self.sorted_node_ids();
+
+        // Order the nodes by when a depth first search finishes with them
+        let mut visited = HashSet::new();
+        let mut finished = Vec::new();
+
+        for &root in &node_ids{
+            if !visited.insert(root){
+                continue;
+            }
+
+            let mut calls = vec![(root, 0)];
+            while let Some(&mut (node_id, ref mut next_edge)) = calls.last_mut(){
+                let edges = self.edges(node_id).as_slice();
+
+                if *next_edge < edges.len(){
+                    let to = edges[*next_edge].to;
+                    *next_edge += 1;
+
+                    if self.contains_node(to) && visited.insert(to){
+                        calls.push((to, 0));
+                    }
+                }else{
+                    calls.pop();
+                    finished.push(node_id);
+                }
+            }
+        }
+
+        let mut reversed: HashMap<u32, Vec<u32>> = HashMap::new();
+        for &node_id in &node_ids{
+            for edge in self.edges(node_id){
+                reversed.entry(edge.to).or_default().push(edge.from);
+            }
+        }
+
+        // Searching the reversed graph from the last node to finish collects one component at a time
+        let mut components = HashMap::new();
+        let mut count = 0;
+
+        for &root in finished.iter().rev(){
+            if components.contains_key(&root){
+                continue;
+            }
+
+            let mut stack = vec![root];
+            components.insert(root, count);
+
+            while let Some(node_id) = stack.pop(){
+                if let Some(predecessors) = reversed.get(&node_id){
+                    for &predecessor in predecessors{
+                        if let Entry::Vacant(entry) = components.entry(predecessor){
+                            entry.insert(count);
+                            stack.push(predecessor);
+                        }
+                    }
+                }
+            }
+
+            count += 1;
+        }
+
+        components
+    }
+
+    /// Build the condensation of the graph, where every strongly connected component is shrunk
+    /// into a single node.  The ID of each node in the new graph is its component ID from
+    /// `tarjan_scc`, and its data is the sorted IDs of the nodes in the component.  Every edge
+    /// between two different components is kept with its weight, so the result has no cycles.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node1, node2);
+    /// graph.add_directed_edge(node2, node1);
+    /// graph.add_directed_edge(node2, node3);
+    ///
+    /// let condensation = graph.condensation();
+    ///
+    /// assert_eq!(condensation.node_count(), 2);
+    /// assert_eq!(condensation.topological_sort(), Ok(vec![0, 1]));
+    /// ```
+    pub fn condensation(&self) -> Graph<Vec<u32>, E> where E: Clone{
+        let components = self.tarjan_scc();
+        let node_ids = self.sorted_node_ids();
+
+        let mut members = vec![Vec::new(); node_ids.len()];
+        for &node_id in &node_ids{
+            members[components[&node_id] as usize].push(node_id);
+        }
+
+        let mut condensation = Graph::new_weighted();
+        for component in members.into_iter().take_while(|component| !component.is_empty()){
+            condensation.add_node(component);
+        }
+
+        for &node_id in &node_ids{
+            for edge in self.edges(node_id){
+                if let Some(&to) = components.get(&edge.to){
+                    if to != components[&node_id]{
+                        condensation.add_weighted_directed_edge(components[&node_id], to, edge.weight.clone());
+                    }
+                }
+            }
+        }
+
+        condensation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_tarjan_scc(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+        let fifth_node = graph.add_node(5);
+
+        graph.add_directed_edge(zeroth_node, first_node);
+        graph.add_directed_edge(first_node, second_node);
+        graph.add_directed_edge(second_node, zeroth_node);
+        graph.add_directed_edge(second_node, third_node);
+        graph.add_directed_edge(third_node, fourth_node);
+        graph.add_directed_edge(fourth_node, third_node);
+        graph.add_directed_edge(fifth_node, fourth_node);
+
+        let components = graph.tarjan_scc();
+
+        assert_eq!(components[&zeroth_node], components[&first_node]);
+        assert_eq!(components[&zeroth_node], components[&second_node]);
+        assert_eq!(components[&third_node], components[&fourth_node]);
+
+        assert!(components[&zeroth_node] < components[&third_node]);
+        assert!(components[&fifth_node] < components[&third_node]);
+        assert_ne!(components[&zeroth_node], components[&fifth_node]);
+
+        let mut ids: Vec<u32> = components.values().cloned().collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_kosaraju_scc(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+        let fifth_node = graph.add_node(5);
+
+        graph.add_directed_edge(zeroth_node, first_node);
+        graph.add_directed_edge(first_node, second_node);
+        graph.add_directed_edge(second_node, zeroth_node);
+        graph.add_directed_edge(second_node, third_node);
+        graph.add_directed_edge(third_node, fourth_node);
+        graph.add_directed_edge(fourth_node, third_node);
+        graph.add_directed_edge(fifth_node, fourth_node);
+
+        let components = graph.kosaraju_scc();
+
+        assert_eq!(components[&zeroth_node], components[&first_node]);
+        assert_eq!(components[&zeroth_node], components[&second_node]);
+        assert_eq!(components[&third_node], components[&fourth_node]);
+
+        assert!(components[&zeroth_node] < components[&third_node]);
+        assert!(components[&fifth_node] < components[&third_node]);
+        assert_ne!(components[&zeroth_node], components[&fifth_node]);
+
+        // Both algorithms find the same components
+        let tarjan = graph.tarjan_scc();
+        for a in graph.node_ids(){
+            for b in graph.node_ids(){
+                assert_eq!(components[&a] == components[&b], tarjan[&a] == tarjan[&b]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_condensation(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_weighted_directed_edge(zeroth_node, first_node, 1);
+        graph.add_weighted_directed_edge(first_node, zeroth_node, 2);
+        graph.add_weighted_directed_edge(first_node, second_node, 3);
+        graph.add_weighted_directed_edge(second_node, third_node, 4);
+        graph.add_weighted_directed_edge(third_node, second_node, 5);
+        graph.add_weighted_directed_edge(zeroth_node, third_node, 6);
+
+        let condensation = graph.condensation();
+
+        assert_eq!(condensation.node_count(), 2);
+        assert_eq!(condensation[0], vec![zeroth_node, first_node]);
+        assert_eq!(condensation[1], vec![second_node, third_node]);
+
+        let edges: Vec<_> = condensation.edges(0).collect();
+        assert_eq!(edges.len(), 2);
+        assert_eq!((edges[0].to(), *edges[0].weight()), (1, 6));
+        assert_eq!((edges[1].to(), *edges[1].weight()), (1, 3));
+        assert_eq!(condensation.edges(1).count(), 0);
+    }
+}
@@ -0,0 +1,410 @@
+use std::collections::{HashMap, HashSet};
+
+use graph::{EdgeId, Graph, Undirected};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A node in a block-cut tree
+pub enum BlockCutNode{
+    /// A biconnected component, holding the sorted IDs of its nodes
+    Block(Vec<u32>),
+    /// An articulation point, holding its ID in the original graph
+    CutVertex(u32)
+}
+
+/// Everything found by a single depth first search over an undirected graph
+struct Biconnectivity{
+    bridges: Vec<(u32, u32)>,
+    articulation_points: Vec<u32>,
+    blocks: Vec<Vec<u32>>
+}
+
+/// A node being visited by the depth first search
+struct Call{
+    node: u32,
+    parent: Option<u32>,
+    // The edge used to reach this node, which is not a back edge even though it leads to the
+    // parent.  Parallel edges to the parent have other IDs, so they still count.
+    parent_edge: Option<EdgeId>,
+    next_edge: usize,
+    children: usize
+}
+
+impl<T, E> Graph<T, E, Undirected>{
+
+    /// Find the bridges of an undirected graph, the edges whose removal disconnects the graph.
+    /// Each bridge is given as `(smaller ID, larger ID)`, and the bridges are sorted.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    /// let node4 = graph.add_node(4);
+    ///
+    /// graph.add_undirected_edge(node1, node2);
+    /// graph.add_undirected_edge(node2, node3);
+    /// graph.add_undirected_edge(node3, node1);
+    /// graph.add_undirected_edge(node3, node4);
+    ///
+    /// assert_eq!(graph.bridges(), vec![(node3, node4)]);
+    /// ```
+    pub fn bridges(&self) -> Vec<(u32, u32)>{
+        self.biconnectivity().bridges
+    }
+
+    /// Find the articulation points of an undirected graph, the nodes whose removal
+    /// disconnects the graph.  The IDs are sorted.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_undirected_edge(node1, node2);
+    /// graph.add_undirected_edge(node2, node3);
+    ///
+    /// assert_eq!(graph.articulation_points(), vec![node2]);
+    /// ```
+    pub fn articulation_points(&self) -> Vec<u32>{
+        self.biconnectivity().articulation_points
+    }
+
+    /// Find the biconnected components of an undirected graph, the largest pieces that stay
+    /// connected after removing any one node.  Each component is given as its sorted node IDs.
+    /// Components share their articulation points, and a node with no edges is a component
+    /// on its own.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    /// let node4 = graph.add_node(4);
+    ///
+    /// graph.add_undirected_edge(node1, node2);
+    /// graph.add_undirected_edge(node2, node3);
+    /// graph.add_undirected_edge(node3, node1);
+    /// graph.add_undirected_edge(node3, node4);
+    ///
+    /// let components = graph.biconnected_components();
+    ///
+    /// assert_eq!(components, vec![vec![node3, node4], vec![node1, node2, node3]]);
+    /// ```
+    pub fn biconnected_components(&self) -> Vec<Vec<u32>>{
+        self.biconnectivity().blocks
+    }
+
+    /// Build the block-cut tree of an undirected graph.  There is a node for every biconnected
+    /// component, in the order given by `biconnected_components`, followed by a node for every
+    /// articulation point in ascending order.  Each articulation point has an undirected edge to
+    /// every component containing it.  The result is a forest if the graph is not connected.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// # use kyles_algorithm_crate::graph::connectivity::BlockCutNode;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_undirected_edge(node1, node2);
+    /// graph.add_undirected_edge(node2, node3);
+    ///
+    /// let tree = graph.block_cut_tree();
+    ///
+    /// // Two blocks joined through node 2
+    /// assert_eq!(tree.node_count(), 3);
+    /// assert_eq!(tree.bfs(2).count(), 3);
+    /// ```
+    pub fn block_cut_tree(&self) -> Graph<BlockCutNode, i64, Undirected>{
+        let biconnectivity = self.biconnectivity();
+        let mut tree = Graph::new_undirected();
+
+        let blocks: Vec<u32> = biconnectivity.blocks.iter()
+            .map(|block| tree.add_node(BlockCutNode::Block(block.clone())))
+            .collect();
+
+        for &cut_vertex in &biconnectivity.articulation_points{
+            let tree_node = tree.add_node(BlockCutNode::CutVertex(cut_vertex));
+
+            for (i, block) in biconnectivity.blocks.iter().enumerate(){
+                if block.binary_search(&cut_vertex).is_ok(){
+                    tree.add_undirected_edge(tree_node, blocks[i]);
+                }
+            }
+        }
+
+        tree
+    }
+
+    /// Run the Hopcroft-Tarjan depth first search, tracking the earliest discovered node each
+    /// subtree can reach without using the edge to its parent
+    fn biconnectivity(&self) -> Biconnectivity{
+        let mut discovered: HashMap<u32, usize> = HashMap::new();
+        let mut low: HashMap<u32, usize> = HashMap::new();
+        let mut edge_stack: Vec<(u32, u32)> = Vec::new();
+
+        let mut bridges = Vec::new();
+        let mut articulation_points = HashSet::new();
+        let mut blocks = Vec::new();
+
+        for root in self.sorted_node_ids(){
+            if discovered.contains_key(&root){
+                continue;
+            }
+
+            discovered.insert(root, discovered.len());
+            low.insert(root, discovered[&root]);
+
+            let mut calls = vec![Call{node: root, parent: None, parent_edge: None, next_edge: 0, children: 0}];
+            let mut isolated = true;
+
+            while let Some(call) = calls.last_mut(){
+                let node_id = call.node;
+                let edges = self.edges(node_id).as_slice();
+
+                if call.next_edge < edges.len(){
+                    let edge = &edges[call.next_edge];
+                    let to = edge.to;
+                    call.next_edge += 1;
+
+                    if !self.contains_node(to) || to == node_id{
+                        continue;
+                    }
+                    isolated = false;
+
+                    if Some(edge.id) == call.parent_edge{
+                        continue;
+                    }
+
+                    match discovered.get(&to).cloned(){
+                        None => {
+                            call.children += 1;
+                            edge_stack.push((node_id, to));
+                            discovered.insert(to, discovered.len());
+                            low.insert(to, discovered[&to]);
+                            calls.push(Call{node: to, parent: Some(node_id), parent_edge: Some(edge.id), next_edge: 0, children: 0});
+                        },
+                        Some(to_discovered) if to_discovered < discovered[&node_id] => {
+                            // Back edge to an ancestor
+                            edge_stack.push((node_id, to));
+                            let new_low = low[&node_id].min(to_discovered);
+                            low.insert(node_id, new_low);
+                        },
+                        // The other half of a back edge that was already seen from below
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                let finished = calls.pop().unwrap();
+                let parent = match finished.parent{
+                    Some(parent) => parent,
+                    None => {
+                        // The root only separates the graph if it has several subtrees
+                        if finished.children > 1{
+                            articulation_points.insert(node_id);
+                        }
+                        continue;
+                    }
+                };
+
+                let new_low = low[&parent].min(low[&node_id]);
+                low.insert(parent, new_low);
+
+                if low[&node_id] > discovered[&parent]{
+                    bridges.push((parent.min(node_id), parent.max(node_id)));
+                }
+
+                // Nothing below this node reaches above its parent, so the edges pushed since
+                // the tree edge into it form a block
+                if low[&node_id] >= discovered[&parent]{
+                    if calls.len() > 1{
+                        articulation_points.insert(parent);
+                    }
+
+                    let mut block = HashSet::new();
+                    while let Some((from, to)) = edge_stack.pop(){
+                        block.insert(from);
+                        block.insert(to);
+
+                        if (from, to) == (parent, node_id){
+                            break;
+                        }
+                    }
+
+                    let mut block: Vec<u32> = block.into_iter().collect();
+                    block.sort();
+                    blocks.push(block);
+                }
+            }
+
+            if isolated{
+                blocks.push(vec![root]);
+            }
+        }
+
+        let mut articulation_points: Vec<u32> = articulation_points.into_iter().collect();
+        articulation_points.sort();
+        bridges.sort();
+
+        Biconnectivity{bridges, articulation_points, blocks}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_bridges(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+        let fifth_node = graph.add_node(5);
+
+        graph.add_undirected_edge(zeroth_node, first_node);
+        graph.add_undirected_edge(first_node, second_node);
+        graph.add_undirected_edge(second_node, zeroth_node);
+        graph.add_undirected_edge(first_node, third_node);
+        graph.add_undirected_edge(third_node, fourth_node);
+
+        // Parallel edges are never bridges
+        graph.add_undirected_edge(fourth_node, fifth_node);
+        graph.add_undirected_edge(fourth_node, fifth_node);
+
+        assert_eq!(graph.bridges(), vec![(first_node, third_node), (third_node, fourth_node)]);
+    }
+
+    #[test]
+    fn test_articulation_points(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+        let fifth_node = graph.add_node(5);
+
+        graph.add_undirected_edge(zeroth_node, first_node);
+        graph.add_undirected_edge(first_node, second_node);
+        graph.add_undirected_edge(second_node, zeroth_node);
+        graph.add_undirected_edge(first_node, third_node);
+        graph.add_undirected_edge(third_node, fourth_node);
+        graph.add_undirected_edge(fourth_node, fifth_node);
+        graph.add_undirected_edge(fifth_node, third_node);
+
+        assert_eq!(graph.articulation_points(), vec![first_node, third_node]);
+
+        // The root of the search is an articulation point when it has two subtrees
+        let mut graph2 = Graph::new_undirected();
+
+        let node1_id = graph2.add_node(1);
+        let node2_id = graph2.add_node(2);
+        let node3_id = graph2.add_node(3);
+
+        graph2.add_undirected_edge(node1_id, node2_id);
+        graph2.add_undirected_edge(node1_id, node3_id);
+
+        assert_eq!(graph2.articulation_points(), vec![node1_id]);
+    }
+
+    #[test]
+    fn test_biconnected_components(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+        let fifth_node = graph.add_node(5);
+        let sixth_node = graph.add_node(6);
+
+        graph.add_undirected_edge(zeroth_node, first_node);
+        graph.add_undirected_edge(first_node, second_node);
+        graph.add_undirected_edge(second_node, zeroth_node);
+        graph.add_undirected_edge(first_node, third_node);
+        graph.add_undirected_edge(third_node, fourth_node);
+        graph.add_undirected_edge(fourth_node, fifth_node);
+        graph.add_undirected_edge(fifth_node, third_node);
+
+        let components = graph.biconnected_components();
+
+        let expected = vec![
+            vec![third_node, fourth_node, fifth_node],
+            vec![first_node, third_node],
+            vec![zeroth_node, first_node, second_node],
+            vec![sixth_node],
+        ];
+
+        assert_eq!(components, expected);
+    }
+
+    #[test]
+    fn test_block_cut_tree(){
+        use graph::Graph;
+        use graph::connectivity::BlockCutNode;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+        let fifth_node = graph.add_node(5);
+
+        graph.add_undirected_edge(zeroth_node, first_node);
+        graph.add_undirected_edge(first_node, second_node);
+        graph.add_undirected_edge(second_node, zeroth_node);
+        graph.add_undirected_edge(first_node, third_node);
+        graph.add_undirected_edge(third_node, fourth_node);
+        graph.add_undirected_edge(fourth_node, fifth_node);
+        graph.add_undirected_edge(fifth_node, third_node);
+
+        let tree = graph.block_cut_tree();
+
+        // Three blocks and two cut vertices
+        assert_eq!(tree.node_count(), 5);
+        assert_eq!(tree[0], BlockCutNode::Block(vec![third_node, fourth_node, fifth_node]));
+        assert_eq!(tree[3], BlockCutNode::CutVertex(first_node));
+        assert_eq!(tree[4], BlockCutNode::CutVertex(third_node));
+
+        // Node 1 joins the triangle and the bridge, node 3 joins the bridge and the other triangle
+        let neighbours: Vec<u32> = tree.neighbors(3).collect();
+        assert_eq!(neighbours, vec![1, 2]);
+
+        let neighbours: Vec<u32> = tree.neighbors(4).collect();
+        assert_eq!(neighbours, vec![0, 1]);
+
+        // Four edges make the five nodes a tree
+        assert_eq!(tree.bfs(0).count(), 5);
+    }
+}
@@ -0,0 +1,446 @@
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::marker::PhantomData;
+use std::ops::{Index, Range};
+use std::slice;
+
+use graph::{Adjacency, Directed, Direction, Graph, Node, Weight, Weighted};
+use graph::page_rank::page_rank_by_index;
+use graph::shortest_path::{bellman_ford_from, Lowest, NegativeCycle, ShortestPaths};
+use graph::traversal::Visit;
+
+/// Immutable graph stored in compressed sparse row form, made by `Graph::freeze`.  Nodes are
+/// kept in ascending order of ID and the edges of each node sit next to each other in flat
+/// arrays, so following edges reads memory in order instead of hopping between hash map
+/// entries.  Edges are looked up by node ID like in `Graph`.
+///
+/// Only the read heavy algorithms have a fast path here: `bfs`, `dijkstra`, `bellman_ford` and
+/// `page_rank`, plus `all_pairs::floyd_warshall` and `all_pairs::transitive_closure` through
+/// the `Adjacency` trait.  Everything else, such as depth first search, topological sorts,
+/// strongly connected components, flows and matchings, is only on `Graph`.
+pub struct CsrGraph<T, E = i64, D = Directed>{
+    // The ID of the node at each position, in ascending order
+    node_ids: Vec<u32>,
+    data: Vec<T>,
+    // The edges of the node at position i are at offsets[i]..offsets[i + 1]
+    offsets: Vec<usize>,
+    // The position of the node each edge goes to
+    targets: Vec<u32>,
+    weights: Vec<E>,
+    edge_count: usize,
+    direction: PhantomData<D>
+}
+
+/// Iterator over the IDs of the nodes the edges of a node go to, made by `CsrGraph::neighbors`
+pub struct CsrNeighbors<'a>{
+    node_ids: &'a [u32],
+    targets: slice::Iter<'a, u32>
+}
+
+/// Iterator over the edges of a node as the ID of the node each goes to and its weight, made by
+/// `CsrGraph::edges`
+pub struct CsrEdges<'a, E: 'a = i64>{
+    node_ids: &'a [u32],
+    targets: slice::Iter<'a, u32>,
+    weights: slice::Iter<'a, E>
+}
+
+/// Lazy breadth first traversal of the nodes reachable from a start node of a `CsrGraph`
+pub struct CsrBfs<'a, T: 'a, E: 'a = i64, D: 'a = Directed>{
+    graph: &'a CsrGraph<T, E, D>,
+    // Each entry is the position of a node and its visit
+    queue: VecDeque<(usize, Visit)>,
+    discovered: Vec<bool>
+}
+
+impl<T, E, D: Direction> Graph<T, E, D>{
+
+    /// Turn the graph into an immutable `CsrGraph` holding the same nodes and edges, which is
+    /// faster to run read only algorithms on.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// graph.add_weighted_directed_edge(node1, node2, 5);
+    ///
+    /// let frozen = graph.freeze();
+    ///
+    /// assert_eq!(frozen.node_count(), 2);
+    /// assert_eq!(frozen.edges(node1).collect::<Vec<(u32, &i64)>>(), vec![(node2, &5)]);
+    /// assert_eq!(frozen.dijkstra(node1).distance_to(node2), Some(5));
+    /// ```
+    pub fn freeze(self) -> CsrGraph<T, E, D>{
+        let mut nodes: Vec<(u32, Node<T, E>)> = self.nodes.into_iter().collect();
+        nodes.sort_by_key(|&(node_id, _)| node_id);
+
+        let indices: HashMap<u32, u32> = nodes.iter().enumerate().map(|(i, &(node_id, _))| (node_id, i as u32)).collect();
+
+        let mut node_ids = Vec::with_capacity(nodes.len());
+        let mut data = Vec::with_capacity(nodes.len());
+        let mut offsets = Vec::with_capacity(nodes.len() + 1);
+        let mut targets = Vec::new();
+        let mut weights = Vec::new();
+        let mut edge_count = 0;
+
+        offsets.push(0);
+        for (position, (node_id, node)) in nodes.into_iter().enumerate(){
+            for edge in node.edges{
+                if let Some(&target) = indices.get(&edge.to){
+                    // Both nodes of an undirected edge keep it, so count it at the smaller one
+                    if D::is_directed() || position as u32 <= target{
+                        edge_count += 1;
+                    }
+
+                    targets.push(target);
+                    weights.push(edge.weight);
+                }
+            }
+
+            node_ids.push(node_id);
+            data.push(node.data);
+            offsets.push(targets.len());
+        }
+
+        CsrGraph{node_ids, data, offsets, targets, weights, edge_count, direction: PhantomData}
+    }
+}
+
+impl<T, E, D: Direction> CsrGraph<T, E, D>{
+
+    /// Returns the number of nodes in the graph
+    pub fn node_count(&self) -> usize{
+        self.node_ids.len()
+    }
+
+    /// Returns the number of edges in the graph.  An undirected edge counts once.
+    pub fn edge_count(&self) -> usize{
+        self.edge_count
+    }
+
+    /// Returns the IDs of every node in the graph in ascending order
+    pub fn node_ids(&self) -> &[u32]{
+        &self.node_ids
+    }
+
+    /// Get the number of edges going out of a node, or `None` if the node is not in the graph
+    /// # Arguments
+    /// `node` - The ID of the node to count the edges of
+    pub fn out_degree(&self, node: u32) -> Option<usize>{
+        self.position(node).map(|position| self.offsets[position + 1] - self.offsets[position])
+    }
+
+    /// Returns an iterator over the IDs of the nodes the edges of a node go to, in the order
+    /// the edges were added to the graph.  The iterator is empty if the node is not in the
+    /// graph.
+    /// # Arguments
+    /// `node` - The ID of the node to get the neighbours of
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_undirected_edge(node1, node2);
+    /// graph.add_undirected_edge(node3, node1);
+    ///
+    /// let frozen = graph.freeze();
+    ///
+    /// assert_eq!(frozen.neighbors(node1).collect::<Vec<u32>>(), vec![node2, node3]);
+    /// assert_eq!(frozen.edge_count(), 2);
+    /// ```
+    pub fn neighbors(&self, node: u32) -> CsrNeighbors<'_>{
+        CsrNeighbors{node_ids: &self.node_ids, targets: self.targets[self.edge_range(node)].iter()}
+    }
+
+    /// Returns an iterator over the edges going out of a node, each as the ID of the node it
+    /// goes to and its weight.  The iterator is empty if the node is not in the graph.
+    /// # Arguments
+    /// `node` - The ID of the node to get the edges of
+    pub fn edges(&self, node: u32) -> CsrEdges<'_, E>{
+        let range = self.edge_range(node);
+
+        CsrEdges{node_ids: &self.node_ids, targets: self.targets[range.clone()].iter(), weights: self.weights[range].iter()}
+    }
+
+    /// Returns an iterator over the nodes reachable from `start` in breadth first order, in the
+    /// same order as `Graph::bfs`
+    /// # Arguments
+    ///
+    /// `start` - The ID of the node to start the traversal at
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node1, node2);
+    /// graph.add_directed_edge(node2, node3);
+    ///
+    /// let frozen = graph.freeze();
+    ///
+    /// let order: Vec<(u32, u32)> = frozen.bfs(node1).map(|v| (v.node, v.depth)).collect();
+    ///
+    /// assert_eq!(order, vec![(node1, 0), (node2, 1), (node3, 2)]);
+    /// ```
+    pub fn bfs(&self, start: u32) -> CsrBfs<'_, T, E, D>{
+        let mut queue = VecDeque::new();
+        let mut discovered = vec![false; self.node_ids.len()];
+
+        if let Some(position) = self.position(start){
+            queue.push_back((position, Visit{node: start, depth: 0, parent: None}));
+            discovered[position] = true;
+        }
+
+        CsrBfs{graph: self, queue, discovered}
+    }
+
+    /// Rank the nodes of the graph with PageRank, giving the same ranks as `Graph::page_rank`
+    /// # Arguments
+    ///
+    /// `damping` - The chance of following an edge rather than jumping, usually 0.85
+    /// `iterations` - The number of rounds to run
+    pub fn page_rank(&self, damping: f64, iterations: usize) -> HashMap<u32, f64>{
+        let ranks = page_rank_by_index(self.node_ids.len(), damping, iterations, |node| {
+            self.targets[self.offsets[node]..self.offsets[node + 1]].iter().map(|&target| target as usize)
+        });
+
+        self.node_ids.iter().cloned().zip(ranks).collect()
+    }
+
+    /// Find the position of a node in the node arrays
+    fn position(&self, node: u32) -> Option<usize>{
+        self.node_ids.binary_search(&node).ok()
+    }
+
+    /// Find the range of the edge arrays holding the edges of a node, which is empty if the
+    /// node is not in the graph
+    fn edge_range(&self, node: u32) -> Range<usize>{
+        match self.position(node){
+            Some(position) => self.offsets[position]..self.offsets[position + 1],
+            None => 0..0
+        }
+    }
+}
+
+impl<T, E: Weighted, D: Direction> CsrGraph<T, E, D>{
+
+    /// Find the shortest paths from `source` to every reachable node using Dijkstra's
+    /// algorithm, giving the same paths as `Graph::dijkstra`
+    /// # Arguments
+    ///
+    /// `source` - The ID of the node to start the search from
+    ///
+    /// # Panics
+    ///
+    /// Panics if a reachable edge has a negative weight
+    pub fn dijkstra(&self, source: u32) -> ShortestPaths<E::Weight>{
+        let mut distances: Vec<Option<E::Weight>> = vec![None; self.node_ids.len()];
+        let mut predecessors: Vec<Option<usize>> = vec![None; self.node_ids.len()];
+        let mut heap = BinaryHeap::new();
+
+        if let Some(position) = self.position(source){
+            distances[position] = Some(E::Weight::zero());
+            heap.push(Lowest((E::Weight::zero(), position)));
+        }
+
+        while let Some(Lowest((distance, node))) = heap.pop(){
+
+            // Skip entries made stale by a shorter path found later
+            if distances[node].is_some_and(|best| distance > best){
+                continue;
+            }
+
+            for i in self.offsets[node]..self.offsets[node + 1]{
+                let target = self.targets[i] as usize;
+
+                let edge_weight = self.weights[i].weight();
+                assert!(edge_weight >= E::Weight::zero(), "dijkstra does not support negative edge weights");
+
+                let new_distance = distance + edge_weight;
+                if distances[target].is_none_or(|old_distance| new_distance < old_distance){
+                    distances[target] = Some(new_distance);
+                    predecessors[target] = Some(node);
+                    heap.push(Lowest((new_distance, target)));
+                }
+            }
+        }
+
+        let distances = (0..self.node_ids.len()).filter_map(|node| distances[node].map(|distance| (self.node_ids[node], distance))).collect();
+        let predecessors = (0..self.node_ids.len()).filter_map(|node| predecessors[node].map(|before| (self.node_ids[node], self.node_ids[before]))).collect();
+
+        ShortestPaths{source, distances, predecessors}
+    }
+}
+
+impl<T, E: Weighted> CsrGraph<T, E, Directed>{
+
+    /// Find the shortest paths from `source` to every reachable node using the Bellman-Ford
+    /// algorithm, giving the same paths and negative cycles as `Graph::bellman_ford`
+    /// # Arguments
+    ///
+    /// `source` - The ID of the node to start the search from
+    pub fn bellman_ford(&self, source: u32) -> Result<ShortestPaths<E::Weight>, NegativeCycle>{
+        if self.position(source).is_none(){
+            return Ok(ShortestPaths{source, distances: HashMap::new(), predecessors: HashMap::new()});
+        }
+
+        let (distances, predecessors) = bellman_ford_from(self, &[source])?;
+
+        Ok(ShortestPaths{source, distances, predecessors})
+    }
+}
+
+impl<T, E, D: Direction> Adjacency for CsrGraph<T, E, D>{
+    type Payload = E;
+    type Direction = D;
+
+    fn node_ids(&self) -> Vec<u32>{
+        self.node_ids.clone()
+    }
+
+    fn for_each_edge<F: FnMut(u32, &E)>(&self, node: u32, mut visit: F){
+        for (to, weight) in self.edges(node){
+            visit(to, weight);
+        }
+    }
+}
+
+impl<T, E, D> Index<u32> for CsrGraph<T, E, D>{
+    type Output = T;
+
+    /// Returns the data of the node with the given ID
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in the graph
+    fn index(&self, node: u32) -> &T{
+        match self.node_ids.binary_search(&node){
+            Ok(position) => &self.data[position],
+            Err(_) => panic!("node {} is not in the graph", node)
+        }
+    }
+}
+
+impl<'a> Iterator for CsrNeighbors<'a>{
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32>{
+        self.targets.next().map(|&target| self.node_ids[target as usize])
+    }
+}
+
+impl<'a, E> Iterator for CsrEdges<'a, E>{
+    type Item = (u32, &'a E);
+
+    fn next(&mut self) -> Option<(u32, &'a E)>{
+        let target = self.targets.next()?;
+        let weight = self.weights.next()?;
+
+        Some((self.node_ids[*target as usize], weight))
+    }
+}
+
+impl<'a, T, E, D> Iterator for CsrBfs<'a, T, E, D>{
+    type Item = Visit;
+
+    fn next(&mut self) -> Option<Visit>{
+        let (node, visit) = self.queue.pop_front()?;
+
+        // Queue up every neighbour that has not been seen yet
+        for &target in &self.graph.targets[self.graph.offsets[node]..self.graph.offsets[node + 1]]{
+            let target = target as usize;
+
+            if !self.discovered[target]{
+                self.discovered[target] = true;
+                self.queue.push_back((target, Visit{node: self.graph.node_ids[target], depth: visit.depth + 1, parent: Some(visit.node)}));
+            }
+        }
+
+        Some(visit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use graph::Graph;
+    use graph::test_util::random_graph;
+
+    #[test]
+    fn test_freeze_matches_graph(){
+        for seed in 0..10{
+            let mut graph = random_graph(30, 80, seed, 0..20);
+
+            // Gaps in the node IDs are handled
+            graph.remove_node(3);
+
+            let node_ids = graph.node_ids();
+            let edge_count = graph.edge_count();
+            let bfs: Vec<_> = graph.bfs(0).collect();
+            let dijkstra = graph.dijkstra(0);
+            let bellman_ford = graph.bellman_ford(0);
+            let page_rank = graph.page_rank(0.85, 20);
+            let neighbors: Vec<Vec<u32>> = node_ids.iter().map(|&node_id| graph.neighbors(node_id).collect()).collect();
+
+            let frozen = graph.freeze();
+
+            assert_eq!(frozen.node_ids(), &node_ids[..]);
+            assert_eq!(frozen.edge_count(), edge_count);
+            assert_eq!(frozen.bfs(0).collect::<Vec<_>>(), bfs);
+            assert_eq!(frozen.dijkstra(0), dijkstra);
+            assert_eq!(frozen.bellman_ford(0), bellman_ford);
+
+            let frozen_page_rank = frozen.page_rank(0.85, 20);
+            for node_id in &node_ids{
+                assert!((frozen_page_rank[node_id] - page_rank[node_id]).abs() < 1e-12);
+                assert_eq!(frozen[*node_id], *node_id);
+            }
+
+            for (i, &node_id) in node_ids.iter().enumerate(){
+                assert_eq!(frozen.neighbors(node_id).collect::<Vec<u32>>(), neighbors[i]);
+                assert_eq!(frozen.out_degree(node_id), Some(neighbors[i].len()));
+            }
+
+            assert_eq!(frozen.out_degree(3), None);
+            assert_eq!(frozen.neighbors(3).count(), 0);
+            assert_eq!(frozen.bfs(3).count(), 0);
+            assert!(frozen.dijkstra(3).distances.is_empty());
+            assert!(frozen.bellman_ford(3).unwrap().distances.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_freeze_undirected(){
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_weighted_undirected_edge(zeroth_node, first_node, 4);
+        graph.add_weighted_undirected_edge(first_node, second_node, 2);
+        graph.add_weighted_undirected_edge(second_node, second_node, 1);
+
+        let frozen = graph.freeze();
+
+        assert_eq!(frozen.edge_count(), 3);
+        assert_eq!(frozen.edges(first_node).collect::<Vec<(u32, &i64)>>(), vec![(zeroth_node, &4), (second_node, &2)]);
+        assert_eq!(frozen.edges(second_node).collect::<Vec<(u32, &i64)>>(), vec![(first_node, &2), (second_node, &1)]);
+        assert_eq!(frozen.dijkstra(second_node).distance_to(zeroth_node), Some(6));
+        assert_eq!(frozen.dijkstra(second_node).path_to(zeroth_node), Some(vec![second_node, first_node, zeroth_node]));
+    }
+}
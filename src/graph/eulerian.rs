@@ -0,0 +1,433 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use graph::{Directed, Graph, Undirected};
+use union_find::UnionFind;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Error explaining why a graph has no Eulerian path or circuit
+pub enum EulerianError{
+    /// In a directed graph, these nodes have more edges going out than coming in or the other
+    /// way round, beyond what a path allows.  Each entry is a node and its out-degree minus
+    /// its in-degree.
+    Imbalanced{
+        /// The nodes with unequal in and out degrees, sorted by ID
+        imbalances: Vec<(u32, i64)>
+    },
+    /// In an undirected graph, these nodes touch an odd number of edge ends, beyond what a
+    /// path allows
+    OddDegree{
+        /// The sorted IDs of the nodes with odd degree
+        nodes: Vec<u32>
+    },
+    /// The edges do not all connect to each other, so no single walk can use them all
+    Disconnected{
+        /// The sorted IDs of the nodes touching each connected group of edges, ordered by
+        /// their smallest ID
+        components: Vec<Vec<u32>>
+    }
+}
+
+impl fmt::Display for EulerianError{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        match *self{
+            EulerianError::Imbalanced{ref imbalances} => write!(f, "nodes have unequal in and out degrees (node, out - in): {:?}", imbalances),
+            EulerianError::OddDegree{ref nodes} => write!(f, "nodes have odd degree: {:?}", nodes),
+            EulerianError::Disconnected{ref components} => write!(f, "edges are split into {} disconnected groups: {:?}", components.len(), components)
+        }
+    }
+}
+
+impl Error for EulerianError{}
+
+impl<T, E> Graph<T, E, Directed>{
+
+    /// Find a path that follows every directed edge exactly once, using Hierholzer's algorithm.
+    /// Returns the nodes in the order they are visited, which has one more entry than there
+    /// are edges, or an empty path if there are no edges.  Returns an `EulerianError`
+    /// explaining why if there is no such path.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node2, node1);
+    /// graph.add_directed_edge(node1, node2);
+    /// graph.add_directed_edge(node2, node3);
+    ///
+    /// assert_eq!(graph.eulerian_path(), Ok(vec![node2, node1, node2, node3]));
+    /// ```
+    pub fn eulerian_path(&self) -> Result<Vec<u32>, EulerianError>{
+        self.directed_eulerian(false)
+    }
+
+    /// Find a closed walk that follows every directed edge exactly once, using Hierholzer's
+    /// algorithm.  Returns the nodes in the order they are visited, starting and ending at the
+    /// same node, or an empty circuit if there are no edges.  Returns an `EulerianError`
+    /// explaining why if there is no such circuit.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// # use kyles_algorithm_crate::graph::eulerian::EulerianError;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node1, node2);
+    /// graph.add_directed_edge(node2, node3);
+    ///
+    /// assert_eq!(graph.eulerian_circuit(), Err(EulerianError::Imbalanced{imbalances: vec![(node1, 1), (node3, -1)]}));
+    ///
+    /// graph.add_directed_edge(node3, node1);
+    ///
+    /// assert_eq!(graph.eulerian_circuit(), Ok(vec![node1, node2, node3, node1]));
+    /// ```
+    pub fn eulerian_circuit(&self) -> Result<Vec<u32>, EulerianError>{
+        self.directed_eulerian(true)
+    }
+
+    /// Check the in and out degrees and connectivity of the edges, then walk them
+    fn directed_eulerian(&self, circuit: bool) -> Result<Vec<u32>, EulerianError>{
+        let node_ids = self.sorted_node_ids();
+        let indices: HashMap<u32, usize> = node_ids.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+
+        let mut edges = Vec::new();
+        for &node_id in &node_ids{
+            for edge in self.edges(node_id){
+                if let Some(&to) = indices.get(&edge.to){
+                    edges.push((indices[&node_id], to));
+                }
+            }
+        }
+
+        let mut imbalances = Vec::new();
+        for &node_id in &node_ids{
+            let out_degree = self.neighbors(node_id).filter(|to| indices.contains_key(to)).count();
+            let difference = out_degree as i64 - self.in_degree(node_id).unwrap() as i64;
+
+            if difference != 0{
+                imbalances.push((node_id, difference));
+            }
+        }
+
+        // A path may start with one extra edge out and end with one extra edge in
+        let mut differences: Vec<i64> = imbalances.iter().map(|&(_, difference)| difference).collect();
+        differences.sort();
+
+        if !imbalances.is_empty() && (circuit || differences != vec![-1, 1]){
+            return Err(EulerianError::Imbalanced{imbalances});
+        }
+
+        let start = match imbalances.iter().find(|&&(_, difference)| difference == 1){
+            Some(&(node_id, _)) => indices[&node_id],
+            None => match edges.iter().map(|&(from, _)| from).min(){
+                Some(from) => from,
+                None => return Ok(Vec::new())
+            }
+        };
+
+        eulerian_walk(&node_ids, &edges, true, start)
+    }
+}
+
+impl<T, E> Graph<T, E, Undirected>{
+
+    /// Find a path that follows every undirected edge exactly once, using Hierholzer's
+    /// algorithm.  Returns the nodes in the order they are visited, or an empty path if there are
+    /// no edges.  Returns an `EulerianError` explaining why if there is no such path.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_undirected_edge(node1, node2);
+    /// graph.add_undirected_edge(node2, node3);
+    /// graph.add_undirected_edge(node3, node1);
+    /// graph.add_undirected_edge(node3, node2);
+    ///
+    /// // Only the second and third nodes have odd degree, so the path runs between them
+    /// assert_eq!(graph.eulerian_path(), Ok(vec![node2, node1, node3, node2, node3]));
+    /// ```
+    pub fn eulerian_path(&self) -> Result<Vec<u32>, EulerianError>{
+        self.undirected_eulerian(false)
+    }
+
+    /// Find a closed walk that follows every undirected edge exactly once, using Hierholzer's
+    /// algorithm.  Returns the nodes in the order they are visited, starting and ending at the
+    /// same node, or an empty circuit if there are no edges.  Returns an `EulerianError`
+    /// explaining why if there is no such circuit.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_undirected_edge(node1, node2);
+    /// graph.add_undirected_edge(node2, node3);
+    /// graph.add_undirected_edge(node3, node1);
+    ///
+    /// assert_eq!(graph.eulerian_circuit(), Ok(vec![node1, node2, node3, node1]));
+    /// ```
+    pub fn eulerian_circuit(&self) -> Result<Vec<u32>, EulerianError>{
+        self.undirected_eulerian(true)
+    }
+
+    /// Check the degrees and connectivity of the edges, then walk them
+    fn undirected_eulerian(&self, circuit: bool) -> Result<Vec<u32>, EulerianError>{
+        let node_ids = self.sorted_node_ids();
+        let indices: HashMap<u32, usize> = node_ids.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+
+        // Both nodes of an edge keep it, so only take it from the node with the smaller index
+        let mut edges = Vec::new();
+        let mut degrees = vec![0; node_ids.len()];
+        for (from, node_id) in node_ids.iter().enumerate(){
+            for edge in self.edges(*node_id){
+                if let Some(&to) = indices.get(&edge.to){
+                    if from <= to{
+                        edges.push((from, to));
+                        degrees[from] += 1;
+                        degrees[to] += 1;
+                    }
+                }
+            }
+        }
+        edges.sort();
+
+        let odd: Vec<usize> = (0..node_ids.len()).filter(|&node| degrees[node] % 2 == 1).collect();
+
+        if !odd.is_empty() && (circuit || odd.len() != 2){
+            return Err(EulerianError::OddDegree{nodes: odd.iter().map(|&node| node_ids[node]).collect()});
+        }
+
+        let start = match odd.first(){
+            Some(&node) => node,
+            None => match (0..node_ids.len()).find(|&node| degrees[node] > 0){
+                Some(node) => node,
+                None => return Ok(Vec::new())
+            }
+        };
+
+        eulerian_walk(&node_ids, &edges, false, start)
+    }
+}
+
+/// Check that every edge is reachable from `start` and follow them all with Hierholzer's
+/// algorithm.  Nodes and edges are given by index, with `node_ids` mapping indices back to IDs.
+fn eulerian_walk(node_ids: &[u32], edges: &[(usize, usize)], directed: bool, start: usize) -> Result<Vec<u32>, EulerianError>{
+    let mut sets = UnionFind::new(node_ids.len());
+    let mut adjacency = vec![Vec::new(); node_ids.len()];
+    let mut touched = vec![false; node_ids.len()];
+
+    for (i, &(from, to)) in edges.iter().enumerate(){
+        sets.union(from, to);
+        touched[from] = true;
+        touched[to] = true;
+        adjacency[from].push((to, i));
+        if !directed && from != to{
+            adjacency[to].push((from, i));
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<u32>> = HashMap::new();
+    for node in 0..node_ids.len(){
+        if touched[node]{
+            groups.entry(sets.find(node)).or_default().push(node_ids[node]);
+        }
+    }
+
+    if groups.len() > 1{
+        let mut components: Vec<Vec<u32>> = groups.into_values().collect();
+        components.sort();
+        return Err(EulerianError::Disconnected{components});
+    }
+
+    // Walk until stuck, then back up and splice in detours from nodes with unused edges
+    let mut used = vec![false; edges.len()];
+    let mut next_edge = vec![0; node_ids.len()];
+    let mut stack = vec![start];
+    let mut path = Vec::new();
+
+    while let Some(&node) = stack.last(){
+        match adjacency[node].get(next_edge[node]){
+            Some(&(to, i)) => {
+                next_edge[node] += 1;
+                if !used[i]{
+                    used[i] = true;
+                    stack.push(to);
+                }
+            },
+            None => {
+                stack.pop();
+                path.push(node_ids[node]);
+            }
+        }
+    }
+
+    path.reverse();
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_eulerian_path(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_directed_edge(zeroth_node, first_node);
+        graph.add_directed_edge(first_node, second_node);
+        graph.add_directed_edge(second_node, zeroth_node);
+        graph.add_directed_edge(zeroth_node, third_node);
+        graph.add_directed_edge(third_node, zeroth_node);
+        graph.add_directed_edge(first_node, third_node);
+
+        let path = graph.eulerian_path().unwrap();
+
+        assert_eq!(path, vec![first_node, second_node, zeroth_node, first_node, third_node, zeroth_node, third_node]);
+
+        // Every edge is used once
+        let mut walked: Vec<(u32, u32)> = path.windows(2).map(|pair| (pair[0], pair[1])).collect();
+        let mut edges: Vec<(u32, u32)> = graph.node_ids().into_iter().flat_map(|node_id| graph.edges(node_id).map(|edge| (edge.from(), edge.to()))).collect();
+        walked.sort();
+        edges.sort();
+        assert_eq!(walked, edges);
+
+        graph.add_directed_edge(third_node, first_node);
+
+        let circuit = graph.eulerian_circuit().unwrap();
+
+        assert_eq!(circuit.len(), 8);
+        assert_eq!(circuit[0], circuit[7]);
+        assert_eq!(graph.eulerian_path().unwrap().len(), 8);
+
+        // Graphs without edges have empty paths
+        let mut graph2 = Graph::new();
+        graph2.add_node(1);
+
+        assert_eq!(graph2.eulerian_path(), Ok(vec![]));
+        assert_eq!(graph2.eulerian_circuit(), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_eulerian_errors(){
+        use graph::Graph;
+        use graph::eulerian::EulerianError;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_directed_edge(zeroth_node, first_node);
+        graph.add_directed_edge(zeroth_node, second_node);
+        graph.add_directed_edge(zeroth_node, third_node);
+
+        assert_eq!(graph.eulerian_path(), Err(EulerianError::Imbalanced{imbalances: vec![(zeroth_node, 3), (first_node, -1), (second_node, -1), (third_node, -1)]}));
+
+        let mut undirected_graph = Graph::new_undirected();
+
+        let zeroth_node = undirected_graph.add_node(0);
+        let first_node = undirected_graph.add_node(1);
+        let second_node = undirected_graph.add_node(2);
+        let third_node = undirected_graph.add_node(3);
+
+        undirected_graph.add_undirected_edge(zeroth_node, first_node);
+        undirected_graph.add_undirected_edge(zeroth_node, second_node);
+        undirected_graph.add_undirected_edge(zeroth_node, third_node);
+
+        assert_eq!(undirected_graph.eulerian_path(), Err(EulerianError::OddDegree{nodes: vec![zeroth_node, first_node, second_node, third_node]}));
+
+        // Two separate pairs of double edges are balanced but can not be joined
+        let mut graph2 = Graph::new();
+        let mut undirected_graph2 = Graph::new_undirected();
+
+        for i in 0..5{
+            graph2.add_node(i);
+            undirected_graph2.add_node(i);
+        }
+
+        for _ in 0..2{
+            graph2.add_directed_edge(0, 2);
+            graph2.add_directed_edge(2, 0);
+            graph2.add_directed_edge(1, 3);
+            graph2.add_directed_edge(3, 1);
+            undirected_graph2.add_undirected_edge(0, 2);
+            undirected_graph2.add_undirected_edge(1, 3);
+        }
+
+        let expected = Err(EulerianError::Disconnected{components: vec![vec![0, 2], vec![1, 3]]});
+
+        assert_eq!(graph2.eulerian_circuit(), expected);
+        assert_eq!(undirected_graph2.eulerian_circuit(), expected);
+    }
+
+    #[test]
+    fn test_undirected_eulerian(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+
+        // Two triangles sharing the zeroth node, with a self loop
+        graph.add_undirected_edge(zeroth_node, first_node);
+        graph.add_undirected_edge(first_node, second_node);
+        graph.add_undirected_edge(second_node, zeroth_node);
+        graph.add_undirected_edge(zeroth_node, third_node);
+        graph.add_undirected_edge(third_node, fourth_node);
+        graph.add_undirected_edge(fourth_node, zeroth_node);
+        graph.add_undirected_edge(first_node, first_node);
+
+        let circuit = graph.eulerian_circuit().unwrap();
+
+        assert_eq!(circuit, vec![zeroth_node, first_node, first_node, second_node, zeroth_node, third_node, fourth_node, zeroth_node]);
+
+        graph.add_undirected_edge(second_node, fourth_node);
+
+        let path = graph.eulerian_path().unwrap();
+
+        assert_eq!(path.len(), 9);
+        assert_eq!(path[0], second_node);
+        assert_eq!(path[8], fourth_node);
+
+        // Removing an edge removes it from both of its nodes
+        graph.remove_edge(fourth_node, second_node);
+
+        assert_eq!(graph.eulerian_circuit().unwrap().len(), 8);
+    }
+}
@@ -0,0 +1,1088 @@
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::ops::{Mul, Neg};
+
+use graph::{Adjacency, Directed, Direction, Edge, Graph, Weight, Weighted};
+use graph::shortest_path::{bellman_ford_from, Lowest, NegativeCycle};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The result of a maximum flow search, where edge weights are capacities
+pub struct MaxFlow<E = i64>{
+    /// The total flow from the source to the sink
+    pub value: E,
+    /// The flow on every edge in the graph as `(from, to, flow)`, in ascending order of `from`
+    /// and then in the order the edges were added.  An undirected edge is listed once, at the
+    /// end with the smaller ID, but pointing the way its flow goes.
+    pub edge_flows: Vec<(u32, u32, E)>,
+    /// The sorted IDs of the nodes on the source side of a minimum cut.  The capacity of the
+    /// edges leaving this set equals the value of the flow.
+    pub source_side: Vec<u32>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The result of a minimum cost flow search, where edges have a capacity and a cost
+pub struct MinCostFlow<E = i64>{
+    /// The total flow from the source to the sink
+    pub value: E,
+    /// The total cost of the flow, the sum of the flow times the cost over every edge
+    pub cost: E,
+    /// The flow on every edge in the graph as `(from, to, flow)`, in ascending order of `from`
+    /// and then in the order the edges were added
+    pub edge_flows: Vec<(u32, u32, E)>
+}
+
+/// One direction of an edge in the residual network.  Arcs are stored in pairs, so the
+/// reverse of arc `i` is arc `i ^ 1`.  Only the spare capacity is stored, so the flow on an
+/// edge is the spare capacity of its reverse arc and no amount is ever negative.
+struct Arc<W>{
+    to: usize,
+    residual: W,
+    cost: W
+}
+
+/// The residual network of a graph, with nodes numbered by their position in sorted order
+struct FlowNetwork<W>{
+    node_ids: Vec<u32>,
+    indices: HashMap<u32, usize>,
+    arcs: Vec<Arc<W>>,
+    adjacency: Vec<Vec<usize>>,
+    // The endpoints of each edge in the graph, the index of its forward arc and the spare
+    // capacity its reverse arc starts with, which is the capacity for undirected edges
+    edges: Vec<(u32, u32, usize, W)>
+}
+
+impl<W: Weight> FlowNetwork<W>{
+
+    /// Build the residual network of a graph.  `costs` gives the unit cost of the forward and
+    /// reverse arcs of each edge, so max flow can leave costs at zero and never negate them.
+    fn new<T, E, D, F>(graph: &Graph<T, E, D>, costs: F) -> Self
+        where E: Weighted<Weight = W>, D: Direction, F: Fn(&Edge<E>) -> (W, W) {
+        let node_ids = graph.sorted_node_ids();
+        let indices: HashMap<u32, usize> = node_ids.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+
+        let mut network = FlowNetwork{
+            adjacency: vec![Vec::new(); node_ids.len()],
+            node_ids,
+            indices,
+            arcs: Vec::new(),
+            edges: Vec::new()
+        };
+
+        let mut seen = HashSet::new();
+        for i in 0..network.node_ids.len(){
+            for edge in graph.edges(network.node_ids[i]){
+                let to = match network.indices.get(&edge.to){
+                    Some(&to) => to,
+                    None => continue
+                };
+
+                // Both ends keep an undirected edge, but it is one pipe that can carry flow
+                // either way, so it only gets one pair of arcs
+                if !D::is_directed() && !seen.insert(edge.id){
+                    continue;
+                }
+
+                let capacity = edge.weight.weight();
+                assert!(capacity >= W::zero(), "flow capacities can not be negative");
+
+                let (cost, back_cost) = costs(edge);
+                let back = if D::is_directed() { W::zero() } else { capacity };
+                let arc = network.arcs.len();
+                network.arcs.push(Arc{to, residual: capacity, cost});
+                network.arcs.push(Arc{to: i, residual: back, cost: back_cost});
+                network.adjacency[i].push(arc);
+                network.adjacency[to].push(arc ^ 1);
+                network.edges.push((edge.from, edge.to, arc, back));
+            }
+        }
+
+        network
+    }
+
+    fn residual(&self, arc: usize) -> W{
+        self.arcs[arc].residual
+    }
+
+    fn push(&mut self, arc: usize, amount: W){
+        self.arcs[arc].residual = self.arcs[arc].residual - amount;
+        self.arcs[arc ^ 1].residual = self.arcs[arc ^ 1].residual + amount;
+    }
+
+    /// Returns the arc used to reach each node in a breadth first search of the residual network
+    fn residual_bfs(&self, source: usize) -> Vec<Option<usize>>{
+        let mut reached_by = vec![None; self.node_ids.len()];
+        let mut reached = vec![false; self.node_ids.len()];
+        let mut queue = VecDeque::new();
+
+        reached[source] = true;
+        queue.push_back(source);
+
+        while let Some(node) = queue.pop_front(){
+            for &arc in &self.adjacency[node]{
+                let to = self.arcs[arc].to;
+
+                if !reached[to] && self.residual(arc) > W::zero(){
+                    reached[to] = true;
+                    reached_by[to] = Some(arc);
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        reached_by
+    }
+
+    fn edge_flows(&self) -> Vec<(u32, u32, W)>{
+        self.edges.iter().map(|&(from, to, arc, back)| {
+            // The reverse arc gains spare capacity as flow goes forward and loses it as flow
+            // goes backward
+            let residual = self.residual(arc ^ 1);
+            if residual >= back { (from, to, residual - back) } else { (to, from, back - residual) }
+        }).collect()
+    }
+
+    fn into_max_flow(self, source: usize) -> MaxFlow<W>{
+        let edge_flows = self.edge_flows();
+
+        // The value is the flow leaving the source less any flow coming back into it
+        let source_id = self.node_ids[source];
+        let leaving = edge_flows.iter()
+            .filter(|&&(from, _, _)| from == source_id)
+            .fold(W::zero(), |total, &(_, _, flow)| total + flow);
+        let value = edge_flows.iter()
+            .filter(|&&(_, to, _)| to == source_id)
+            .fold(leaving, |total, &(_, _, flow)| total - flow);
+
+        // Whatever can still be reached from the source is cut off from the sink
+        let reached_by = self.residual_bfs(source);
+        let source_side = (0..self.node_ids.len())
+            .filter(|&node| node == source || reached_by[node].is_some())
+            .map(|node| self.node_ids[node])
+            .collect();
+
+        MaxFlow{value, edge_flows, source_side}
+    }
+
+    fn edmonds_karp(&mut self, source: usize, sink: usize){
+        loop{
+            let reached_by = self.residual_bfs(source);
+            if reached_by[sink].is_none(){
+                break;
+            }
+
+            // Collect the shortest augmenting path and push as much as it allows
+            let mut path = Vec::new();
+            let mut node = sink;
+            while let Some(arc) = reached_by[node]{
+                path.push(arc);
+                node = self.arcs[arc ^ 1].to;
+            }
+
+            let amount = path.iter().map(|&arc| self.residual(arc)).reduce(smaller).unwrap();
+            for arc in path{
+                self.push(arc, amount);
+            }
+        }
+    }
+
+    fn dinic(&mut self, source: usize, sink: usize){
+        loop{
+            // Label each node with its distance from the source in the residual network
+            let mut levels = vec![None; self.node_ids.len()];
+            let mut queue = VecDeque::new();
+
+            levels[source] = Some(0);
+            queue.push_back(source);
+
+            while let Some(node) = queue.pop_front(){
+                for &arc in &self.adjacency[node]{
+                    let to = self.arcs[arc].to;
+
+                    if levels[to].is_none() && self.residual(arc) > W::zero(){
+                        levels[to] = Some(levels[node].unwrap() + 1);
+                        queue.push_back(to);
+                    }
+                }
+            }
+
+            if levels[sink].is_none(){
+                break;
+            }
+
+            // Push a blocking flow along edges that go one level deeper.  No path can carry more
+            // than the spare capacity leaving the source.
+            let limit = self.adjacency[source].iter().fold(W::zero(), |total, &arc| total + self.residual(arc));
+            let mut next_arcs = vec![0; self.node_ids.len()];
+            while self.dinic_augment(source, sink, limit, &levels, &mut next_arcs) > W::zero(){}
+        }
+    }
+
+    /// Find a path from the source to the sink in the level graph and push up to `limit` along
+    /// it.  Returns the amount pushed.
+    fn dinic_augment(&mut self, source: usize, sink: usize, limit: W, levels: &[Option<usize>], next_arcs: &mut [usize]) -> W{
+        // The nodes on the path so far.  Each one leaves along the arc its next arc points at.
+        let mut path = vec![source];
+
+        while let Some(&node) = path.last(){
+            if node == sink{
+                let along = &path[..path.len() - 1];
+                let pushed = along.iter().fold(limit, |amount, &from| {
+                    smaller(amount, self.residual(self.adjacency[from][next_arcs[from]]))
+                });
+
+                for &from in along{
+                    self.push(self.adjacency[from][next_arcs[from]], pushed);
+                }
+
+                return pushed;
+            }
+
+            if next_arcs[node] < self.adjacency[node].len(){
+                let arc = self.adjacency[node][next_arcs[node]];
+                let to = self.arcs[arc].to;
+
+                if self.residual(arc) > W::zero() && levels[to] == levels[node].map(|level| level + 1){
+                    path.push(to);
+                    continue;
+                }
+
+                // This arc is saturated or leads to a lower level
+                next_arcs[node] += 1;
+            }else{
+                // This node is a dead end, so step back and skip the arc that led to it
+                path.pop();
+                if let Some(&parent) = path.last(){
+                    next_arcs[parent] += 1;
+                }
+            }
+        }
+
+        W::zero()
+    }
+
+    fn push_relabel(&mut self, source: usize, sink: usize){
+        let n = self.node_ids.len();
+        let mut heights = vec![0; n];
+        let mut excess = vec![W::zero(); n];
+        let mut next_arcs = vec![0; n];
+        let mut active = VecDeque::new();
+
+        // Flood every edge out of the source
+        heights[source] = n;
+        for i in 0..self.adjacency[source].len(){
+            let arc = self.adjacency[source][i];
+            let amount = self.residual(arc);
+
+            if amount > W::zero(){
+                let to = self.arcs[arc].to;
+                self.push(arc, amount);
+
+                if excess[to] == W::zero() && to != sink && to != source{
+                    active.push_back(to);
+                }
+                excess[to] = excess[to] + amount;
+            }
+        }
+
+        // Discharge active nodes until every node except the source and sink is balanced
+        while let Some(node) = active.pop_front(){
+            while excess[node] > W::zero(){
+                if next_arcs[node] == self.adjacency[node].len(){
+                    // Relabel to just above the lowest neighbour that can still take flow
+                    heights[node] = 1 + self.adjacency[node].iter()
+                        .filter(|&&arc| self.residual(arc) > W::zero())
+                        .map(|&arc| heights[self.arcs[arc].to])
+                        .min()
+                        .unwrap();
+                    next_arcs[node] = 0;
+                    continue;
+                }
+
+                let arc = self.adjacency[node][next_arcs[node]];
+                let to = self.arcs[arc].to;
+
+                if self.residual(arc) > W::zero() && heights[node] == heights[to] + 1{
+                    let amount = smaller(excess[node], self.residual(arc));
+                    self.push(arc, amount);
+
+                    if excess[to] == W::zero() && to != sink && to != source{
+                        active.push_back(to);
+                    }
+                    excess[to] = excess[to] + amount;
+                    excess[node] = excess[node] - amount;
+                }else{
+                    next_arcs[node] += 1;
+                }
+            }
+        }
+    }
+}
+
+impl<W: Weight + Mul<Output = W>> FlowNetwork<W>{
+
+    /// Successive shortest paths: repeatedly push flow along the cheapest path with spare
+    /// capacity until `limit` units have been sent, or until the sink can not be reached if
+    /// there is no limit.  The potentials must make the reduced cost of every arc with spare
+    /// capacity non negative.  Returns the value and cost of the flow.
+    fn successive_shortest_paths(&mut self, source: usize, sink: usize, limit: Option<W>, mut potentials: Vec<W>) -> (W, W){
+        let mut value = W::zero();
+        let mut cost = W::zero();
+
+        while limit.is_none_or(|limit| value < limit){
+            let (distances, reached_by) = self.cheapest_paths(source, &potentials);
+
+            if distances[sink].is_none(){
+                break;
+            }
+
+            // Keeps the reduced costs non negative for the next search
+            for (potential, distance) in potentials.iter_mut().zip(&distances){
+                if let Some(distance) = *distance{
+                    *potential = *potential + distance;
+                }
+            }
+
+            let mut path = Vec::new();
+            let mut node = sink;
+            while let Some(arc) = reached_by[node]{
+                path.push(arc);
+                node = self.arcs[arc ^ 1].to;
+            }
+
+            let mut amount = path.iter().map(|&arc| self.residual(arc)).reduce(smaller).unwrap();
+            if let Some(limit) = limit{
+                amount = smaller(amount, limit - value);
+            }
+
+            for arc in path{
+                self.push(arc, amount);
+                cost = cost + amount * self.arcs[arc].cost;
+            }
+            value = value + amount;
+        }
+
+        (value, cost)
+    }
+
+    /// Dijkstra's algorithm over the arcs with spare capacity, using costs reduced by the
+    /// potentials.  Returns the reduced distance to each node and the arc used to reach it.
+    fn cheapest_paths(&self, source: usize, potentials: &[W]) -> (Vec<Option<W>>, Vec<Option<usize>>){
+        let mut distances = vec![None; self.node_ids.len()];
+        let mut reached_by = vec![None; self.node_ids.len()];
+        let mut heap = BinaryHeap::new();
+
+        distances[source] = Some(W::zero());
+        heap.push(Lowest((W::zero(), source)));
+
+        while let Some(Lowest((distance, node))) = heap.pop(){
+            if distances[node].is_some_and(|best| distance > best){
+                continue;
+            }
+
+            for &arc in &self.adjacency[node]{
+                if self.residual(arc) <= W::zero(){
+                    continue;
+                }
+
+                let to = self.arcs[arc].to;
+                let new_distance = distance + self.arcs[arc].cost + potentials[node] - potentials[to];
+
+                if distances[to].is_none_or(|best| new_distance < best){
+                    distances[to] = Some(new_distance);
+                    reached_by[to] = Some(arc);
+                    heap.push(Lowest((new_distance, to)));
+                }
+            }
+        }
+
+        (distances, reached_by)
+    }
+}
+
+impl<T, E: Weighted, D: Direction> Graph<T, E, D>{
+
+    /// Find a maximum flow from `source` to `sink` using the Edmonds-Karp algorithm, which
+    /// repeatedly pushes flow along the shortest path with spare capacity.  Edge weights are
+    /// used as capacities.  Runs in O(nm^2) time.
+    /// # Arguments
+    ///
+    /// `source` - The ID of the node the flow starts at
+    /// `sink` - The ID of the node the flow ends at
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge has a negative weight.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let source = graph.add_node(1);
+    /// let middle = graph.add_node(2);
+    /// let sink = graph.add_node(3);
+    ///
+    /// graph.add_weighted_directed_edge(source, middle, 5);
+    /// graph.add_weighted_directed_edge(middle, sink, 3);
+    /// graph.add_weighted_directed_edge(source, sink, 2);
+    ///
+    /// let flow = graph.edmonds_karp(source, sink);
+    ///
+    /// assert_eq!(flow.value, 5);
+    /// assert_eq!(flow.source_side, vec![source, middle]);
+    /// ```
+    pub fn edmonds_karp(&self, source: u32, sink: u32) -> MaxFlow<E::Weight>{
+        self.max_flow(source, sink, FlowNetwork::edmonds_karp)
+    }
+
+    /// Find a maximum flow from `source` to `sink` using Dinic's algorithm, which pushes
+    /// blocking flows through layers of nodes at increasing distance from the source.  Edge
+    /// weights are used as capacities.  Runs in O(n^2 m) time, and is fast on unit capacity and
+    /// bipartite graphs.
+    /// # Arguments
+    ///
+    /// `source` - The ID of the node the flow starts at
+    /// `sink` - The ID of the node the flow ends at
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge has a negative weight.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let source = graph.add_node(1);
+    /// let middle = graph.add_node(2);
+    /// let sink = graph.add_node(3);
+    ///
+    /// graph.add_weighted_directed_edge(source, middle, 5);
+    /// graph.add_weighted_directed_edge(middle, sink, 3);
+    /// graph.add_weighted_directed_edge(source, sink, 2);
+    ///
+    /// let flow = graph.dinic(source, sink);
+    ///
+    /// assert_eq!(flow.value, 5);
+    /// assert_eq!(flow.edge_flows, vec![(source, middle, 3), (source, sink, 2), (middle, sink, 3)]);
+    /// ```
+    pub fn dinic(&self, source: u32, sink: u32) -> MaxFlow<E::Weight>{
+        self.max_flow(source, sink, FlowNetwork::dinic)
+    }
+
+    /// Find a maximum flow from `source` to `sink` using the FIFO push-relabel algorithm, which
+    /// floods flow forward and pushes any excess back downhill.  Edge weights are used as
+    /// capacities.  Runs in O(n^3) time, and suits dense graphs.
+    /// # Arguments
+    ///
+    /// `source` - The ID of the node the flow starts at
+    /// `sink` - The ID of the node the flow ends at
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge has a negative weight.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let source = graph.add_node(1);
+    /// let middle = graph.add_node(2);
+    /// let sink = graph.add_node(3);
+    ///
+    /// graph.add_weighted_directed_edge(source, middle, 5);
+    /// graph.add_weighted_directed_edge(middle, sink, 3);
+    /// graph.add_weighted_directed_edge(source, sink, 2);
+    ///
+    /// let flow = graph.push_relabel(source, sink);
+    ///
+    /// assert_eq!(flow.value, 5);
+    /// ```
+    pub fn push_relabel(&self, source: u32, sink: u32) -> MaxFlow<E::Weight>{
+        self.max_flow(source, sink, FlowNetwork::push_relabel)
+    }
+
+    /// Run a max flow algorithm over the residual network of the graph
+    fn max_flow<F>(&self, source: u32, sink: u32, algorithm: F) -> MaxFlow<E::Weight>
+        where F: Fn(&mut FlowNetwork<E::Weight>, usize, usize) {
+        let mut network = FlowNetwork::new(self, |_| (E::Weight::zero(), E::Weight::zero()));
+
+        let source = match network.indices.get(&source){
+            Some(&source) => source,
+            None => return MaxFlow{value: E::Weight::zero(), edge_flows: network.edge_flows(), source_side: Vec::new()}
+        };
+
+        if let Some(&sink) = network.indices.get(&sink){
+            if sink != source{
+                algorithm(&mut network, source, sink);
+            }
+        }
+
+        network.into_max_flow(source)
+    }
+}
+
+impl<T, E: Weighted> Graph<T, E, Directed> where E::Weight: Mul<Output = E::Weight> + Neg<Output = E::Weight>{
+
+    /// Find the cheapest way to send as much flow as possible from `source` to `sink`, using
+    /// successive shortest paths with potentials.  Edge weights are used as capacities, and
+    /// the cost of each unit of flow on an edge is set with `add_costed_directed_edge`.  Edges
+    /// added without a cost are free.
+    /// Costs may be negative, but a `NegativeCycle` error is returned if edges with capacity
+    /// form a cycle of negative cost.  The weight type must be signed, which is checked with a
+    /// `Neg` bound, as sending flow back along an edge refunds its cost.
+    /// # Arguments
+    ///
+    /// `source` - The ID of the node the flow starts at
+    /// `sink` - The ID of the node the flow ends at
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge has a negative capacity.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let source = graph.add_node(1);
+    /// let middle = graph.add_node(2);
+    /// let sink = graph.add_node(3);
+    ///
+    /// // A cheap route with little capacity and an expensive direct route
+    /// graph.add_costed_directed_edge(source, middle, 2, 1);
+    /// graph.add_costed_directed_edge(middle, sink, 2, 1);
+    /// graph.add_costed_directed_edge(source, sink, 2, 5);
+    ///
+    /// let flow = graph.min_cost_max_flow(source, sink).unwrap();
+    ///
+    /// assert_eq!(flow.value, 4);
+    /// assert_eq!(flow.cost, 14);
+    /// ```
+    ///
+    /// Unsigned weights can not refund costs, so they do not compile
+    /// ```compile_fail
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// let mut graph: Graph<u32, u32> = Graph::new_weighted();
+    ///
+    /// let source = graph.add_node(1);
+    /// let sink = graph.add_node(2);
+    ///
+    /// graph.add_costed_directed_edge(source, sink, 2, 1);
+    ///
+    /// let flow = graph.min_cost_max_flow(source, sink);
+    /// ```
+    pub fn min_cost_max_flow(&self, source: u32, sink: u32) -> Result<MinCostFlow<E::Weight>, NegativeCycle>{
+        self.min_cost_flow_limited(source, sink, None)
+    }
+
+    /// Find the cheapest way to send up to `amount` units of flow from `source` to `sink`.
+    /// Less is sent if the network can not carry `amount`.  Works like `min_cost_max_flow`.
+    /// # Arguments
+    ///
+    /// `source` - The ID of the node the flow starts at
+    /// `sink` - The ID of the node the flow ends at
+    /// `amount` - The most flow to send
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge has a negative capacity.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let source = graph.add_node(1);
+    /// let middle = graph.add_node(2);
+    /// let sink = graph.add_node(3);
+    ///
+    /// graph.add_costed_directed_edge(source, middle, 2, 1);
+    /// graph.add_costed_directed_edge(middle, sink, 2, 1);
+    /// graph.add_costed_directed_edge(source, sink, 2, 5);
+    ///
+    /// // Three units use up the cheap route first
+    /// let flow = graph.min_cost_flow(source, sink, 3).unwrap();
+    ///
+    /// assert_eq!(flow.value, 3);
+    /// assert_eq!(flow.cost, 9);
+    /// ```
+    pub fn min_cost_flow(&self, source: u32, sink: u32, amount: E::Weight) -> Result<MinCostFlow<E::Weight>, NegativeCycle>{
+        self.min_cost_flow_limited(source, sink, Some(amount))
+    }
+
+    /// Min cost flow sending up to `limit` units, or as much as possible if there is no limit
+    fn min_cost_flow_limited(&self, source: u32, sink: u32, limit: Option<E::Weight>) -> Result<MinCostFlow<E::Weight>, NegativeCycle>{
+        let mut network = FlowNetwork::new(self, |edge| {
+            let cost = unit_cost(edge);
+            (cost, -cost)
+        });
+
+        // Starting potentials come from the cheapest distance to each node from anywhere
+        let (distances, _) = bellman_ford_from(&Costs(self), &network.node_ids)?;
+        let potentials = network.node_ids.iter().map(|node_id| distances[node_id]).collect();
+
+        let mut value = E::Weight::zero();
+        let mut cost = E::Weight::zero();
+
+        if let (Some(&source), Some(&sink)) = (network.indices.get(&source), network.indices.get(&sink)){
+            if source != sink{
+                let (flow_value, flow_cost) = network.successive_shortest_paths(source, sink, limit, potentials);
+                value = flow_value;
+                cost = flow_cost;
+            }
+        }
+
+        Ok(MinCostFlow{value, cost, edge_flows: network.edge_flows()})
+    }
+}
+
+/// The edges of a graph that have room for flow, weighted by their unit cost, for finding the
+/// starting potentials of a min cost flow with Bellman-Ford
+struct Costs<'a, T: 'a, E: 'a>(&'a Graph<T, E, Directed>);
+
+impl<'a, T, E: Weighted> Adjacency for Costs<'a, T, E>{
+    type Payload = E::Weight;
+    type Direction = Directed;
+
+    fn node_ids(&self) -> Vec<u32>{
+        self.0.sorted_node_ids()
+    }
+
+    fn for_each_edge<F: FnMut(u32, &E::Weight)>(&self, node: u32, mut visit: F){
+        for edge in self.0.edges(node){
+            if edge.weight.weight() > E::Weight::zero() && self.0.contains_node(edge.to){
+                visit(edge.to, &unit_cost(edge));
+            }
+        }
+    }
+}
+
+/// Returns the cost of sending one unit of flow along an edge, which is zero for edges added
+/// without a cost
+fn unit_cost<E: Weighted>(edge: &Edge<E>) -> E::Weight{
+    edge.cost.as_ref().map_or(E::Weight::zero(), Weighted::weight)
+}
+
+/// Returns the smaller of two amounts, which only need a partial order
+fn smaller<W: PartialOrd>(first: W, second: W) -> W{
+    if second < first { second } else { first }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use graph::{Direction, Graph};
+    use graph::flow::MaxFlow;
+    use graph::test_util;
+
+    /// Check that a flow respects capacities and conservation, and that its cut is minimum
+    fn check_flow<D: Direction>(graph: &Graph<u32, i64, D>, source: u32, sink: u32, flow: &MaxFlow){
+        let mut balance = vec![0; graph.node_count()];
+        let mut seen = HashSet::new();
+        let mut i = 0;
+
+        for node_id in graph.sorted_node_ids(){
+            for edge in graph.edges(node_id){
+                // Undirected edges are listed once and may carry flow either way
+                if !seen.insert(edge.id) && !D::is_directed(){
+                    continue;
+                }
+
+                let (from, to, amount) = flow.edge_flows[i];
+                i += 1;
+
+                assert!((from, to) == (edge.from, edge.to) || (!D::is_directed() && (to, from) == (edge.from, edge.to)));
+                assert!(0 <= amount && amount <= edge.weight);
+
+                balance[from as usize] -= amount;
+                balance[to as usize] += amount;
+            }
+        }
+        assert_eq!(i, flow.edge_flows.len());
+
+        for node_id in graph.sorted_node_ids(){
+            if node_id == source{
+                assert_eq!(balance[node_id as usize], -flow.value);
+            }else if node_id == sink{
+                assert_eq!(balance[node_id as usize], flow.value);
+            }else{
+                assert_eq!(balance[node_id as usize], 0);
+            }
+        }
+
+        // The edges leaving the source side are saturated and add up to the flow
+        assert!(flow.source_side.contains(&source));
+        assert!(!flow.source_side.contains(&sink));
+
+        let mut cut = 0;
+        for &node_id in &flow.source_side{
+            for edge in graph.edges(node_id){
+                if !flow.source_side.contains(&edge.to){
+                    cut += edge.weight;
+                }
+            }
+        }
+        assert_eq!(cut, flow.value);
+    }
+
+    /// The example network from Introduction to Algorithms, with a maximum flow of 23
+    fn example_graph() -> Graph<u32>{
+        let mut graph = Graph::new();
+
+        for i in 0..6{
+            graph.add_node(i);
+        }
+
+        graph.add_weighted_directed_edge(0, 1, 16);
+        graph.add_weighted_directed_edge(0, 2, 13);
+        graph.add_weighted_directed_edge(2, 1, 4);
+        graph.add_weighted_directed_edge(1, 3, 12);
+        graph.add_weighted_directed_edge(3, 2, 9);
+        graph.add_weighted_directed_edge(2, 4, 14);
+        graph.add_weighted_directed_edge(4, 3, 7);
+        graph.add_weighted_directed_edge(3, 5, 20);
+        graph.add_weighted_directed_edge(4, 5, 4);
+
+        graph
+    }
+
+    /// A pseudo random graph with `n` nodes and `m` edges
+    fn random_graph(n: u32, m: u32, seed: u64) -> Graph<u32>{
+        test_util::random_graph(n, m, seed, 0..20)
+    }
+
+    #[test]
+    fn test_edmonds_karp(){
+        let graph = example_graph();
+
+        let flow = graph.edmonds_karp(0, 5);
+
+        assert_eq!(flow.value, 23);
+        assert_eq!(flow.source_side, vec![0, 1, 2, 4]);
+        check_flow(&graph, 0, 5, &flow);
+    }
+
+    #[test]
+    fn test_dinic(){
+        let graph = example_graph();
+
+        let flow = graph.dinic(0, 5);
+
+        assert_eq!(flow.value, 23);
+        assert_eq!(flow.source_side, vec![0, 1, 2, 4]);
+        check_flow(&graph, 0, 5, &flow);
+    }
+
+    #[test]
+    fn test_dinic_long_path(){
+        // A path this long would overflow the stack if augmenting recursed once per node
+        let mut graph = Graph::new();
+        for i in 0..200_000{
+            graph.add_node(i);
+        }
+        for i in 0..199_999{
+            graph.add_weighted_directed_edge(i, i + 1, 2 + (i % 3) as i64);
+        }
+
+        let flow = graph.dinic(0, 199_999);
+
+        assert_eq!(flow.value, 2);
+        assert_eq!(flow.source_side, vec![0]);
+    }
+
+    #[test]
+    fn test_push_relabel(){
+        let graph = example_graph();
+
+        let flow = graph.push_relabel(0, 5);
+
+        assert_eq!(flow.value, 23);
+        assert_eq!(flow.source_side, vec![0, 1, 2, 4]);
+        check_flow(&graph, 0, 5, &flow);
+    }
+
+    #[test]
+    fn test_max_flow_undirected(){
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_weighted_undirected_edge(zeroth_node, first_node, 3);
+        graph.add_weighted_undirected_edge(zeroth_node, second_node, 2);
+        graph.add_weighted_undirected_edge(first_node, second_node, 5);
+        graph.add_weighted_undirected_edge(second_node, third_node, 4);
+        graph.add_weighted_undirected_edge(first_node, third_node, 1);
+
+        for flow in [graph.edmonds_karp(zeroth_node, third_node), graph.dinic(zeroth_node, third_node), graph.push_relabel(zeroth_node, third_node)]{
+            assert_eq!(flow.value, 5);
+            assert_eq!(flow.edge_flows.len(), 5);
+            check_flow(&graph, zeroth_node, third_node, &flow);
+        }
+
+        // Going the other way every edge is listed pointing back against how it was added
+        let flow = graph.dinic(third_node, zeroth_node);
+
+        assert_eq!(flow.value, 5);
+        assert!(flow.edge_flows.contains(&(third_node, second_node, 4)));
+        assert!(flow.edge_flows.contains(&(first_node, zeroth_node, 3)));
+        check_flow(&graph, third_node, zeroth_node, &flow);
+    }
+
+    #[test]
+    fn test_max_flow_disconnected(){
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_weighted_directed_edge(zeroth_node, first_node, 3);
+        graph.add_weighted_directed_edge(second_node, first_node, 3);
+
+        for flow in [graph.edmonds_karp(zeroth_node, second_node), graph.dinic(zeroth_node, second_node), graph.push_relabel(zeroth_node, second_node)]{
+            assert_eq!(flow.value, 0);
+            assert_eq!(flow.source_side, vec![zeroth_node, first_node]);
+            check_flow(&graph, zeroth_node, second_node, &flow);
+        }
+
+        // Missing nodes carry no flow
+        assert_eq!(graph.dinic(zeroth_node, 7).value, 0);
+        assert_eq!(graph.dinic(7, zeroth_node).value, 0);
+    }
+
+    #[test]
+    fn test_max_flow_algorithms_agree(){
+        for seed in 0..20{
+            let graph = random_graph(12, 40, seed);
+
+            let edmonds_karp = graph.edmonds_karp(0, 11);
+            let dinic = graph.dinic(0, 11);
+            let push_relabel = graph.push_relabel(0, 11);
+
+            assert_eq!(edmonds_karp.value, dinic.value);
+            assert_eq!(edmonds_karp.value, push_relabel.value);
+
+            check_flow(&graph, 0, 11, &edmonds_karp);
+            check_flow(&graph, 0, 11, &dinic);
+            check_flow(&graph, 0, 11, &push_relabel);
+        }
+    }
+
+    #[test]
+    fn test_min_cost_max_flow(){
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_costed_directed_edge(zeroth_node, first_node, 2, 1);
+        graph.add_costed_directed_edge(zeroth_node, second_node, 1, 2);
+        graph.add_costed_directed_edge(first_node, second_node, 1, 1);
+        graph.add_costed_directed_edge(first_node, third_node, 1, 3);
+        graph.add_costed_directed_edge(second_node, third_node, 2, 1);
+        graph.add_costed_directed_edge(zeroth_node, third_node, 1, 10);
+
+        let flow = graph.min_cost_max_flow(zeroth_node, third_node).unwrap();
+
+        assert_eq!(flow.value, 4);
+        assert_eq!(flow.cost, 20);
+
+        let expected = vec![
+            (zeroth_node, first_node, 2),
+            (zeroth_node, second_node, 1),
+            (zeroth_node, third_node, 1),
+            (first_node, second_node, 1),
+            (first_node, third_node, 1),
+            (second_node, third_node, 2),
+        ];
+        assert_eq!(flow.edge_flows, expected);
+
+        // The flow value matches the plain max flow algorithms
+        assert_eq!(flow.value, graph.dinic(zeroth_node, third_node).value);
+    }
+
+    #[test]
+    fn test_min_cost_flow(){
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_costed_directed_edge(zeroth_node, first_node, 2, 1);
+        graph.add_costed_directed_edge(zeroth_node, second_node, 1, 2);
+        graph.add_costed_directed_edge(first_node, second_node, 1, 1);
+        graph.add_costed_directed_edge(first_node, third_node, 1, 3);
+        graph.add_costed_directed_edge(second_node, third_node, 2, 1);
+        graph.add_costed_directed_edge(zeroth_node, third_node, 1, 10);
+
+        let flow = graph.min_cost_flow(zeroth_node, third_node, 2).unwrap();
+
+        assert_eq!(flow.value, 2);
+        assert_eq!(flow.cost, 6);
+
+        let flow = graph.min_cost_flow(zeroth_node, third_node, 0).unwrap();
+
+        assert_eq!(flow.value, 0);
+        assert_eq!(flow.cost, 0);
+
+        // Missing nodes carry no flow
+        let flow = graph.min_cost_flow(zeroth_node, 7, 2).unwrap();
+
+        assert_eq!(flow.value, 0);
+        assert!(flow.edge_flows.iter().all(|&(_, _, amount)| amount == 0));
+    }
+
+    #[test]
+    fn test_min_cost_flow_negative_costs(){
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_costed_directed_edge(zeroth_node, first_node, 1, -5);
+        graph.add_costed_directed_edge(first_node, second_node, 1, 1);
+        graph.add_costed_directed_edge(zeroth_node, second_node, 1, 1);
+
+        let flow = graph.min_cost_max_flow(zeroth_node, second_node).unwrap();
+
+        assert_eq!(flow.value, 2);
+        assert_eq!(flow.cost, -3);
+
+        // Only cycles of edges with capacity count
+        graph.add_costed_directed_edge(second_node, first_node, 0, -5);
+
+        assert!(graph.min_cost_max_flow(zeroth_node, second_node).is_ok());
+
+        graph.add_costed_directed_edge(second_node, first_node, 1, -2);
+
+        let error = graph.min_cost_max_flow(zeroth_node, second_node).unwrap_err();
+
+        assert_eq!(error.cycle, vec![first_node, second_node]);
+    }
+
+    #[test]
+    fn test_float_capacities(){
+        let mut graph: Graph<u32, f64> = Graph::new_weighted();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_weighted_directed_edge(zeroth_node, first_node, 2.5);
+        graph.add_weighted_directed_edge(zeroth_node, second_node, 1.25);
+        graph.add_weighted_directed_edge(first_node, second_node, 0.5);
+        graph.add_weighted_directed_edge(first_node, third_node, 1.5);
+        graph.add_weighted_directed_edge(second_node, third_node, 2.0);
+
+        for flow in [graph.edmonds_karp(zeroth_node, third_node), graph.dinic(zeroth_node, third_node), graph.push_relabel(zeroth_node, third_node)]{
+            assert_eq!(flow.value, 3.25);
+            assert_eq!(flow.source_side, vec![zeroth_node, first_node]);
+        }
+    }
+
+    #[test]
+    fn test_unsigned_capacities(){
+        for seed in 0..20{
+            let graph = random_graph(12, 40, seed);
+
+            // The same network with unsigned capacities.  Costs play no part in max flow, so
+            // giving some edges one must not change anything.
+            let mut unsigned: Graph<u32, u32> = Graph::new_weighted();
+            for node_id in graph.sorted_node_ids(){
+                unsigned.add_node(node_id);
+            }
+            for node_id in graph.sorted_node_ids(){
+                for edge in graph.edges(node_id){
+                    if edge.weight % 2 == 0{
+                        unsigned.add_costed_directed_edge(edge.from, edge.to, edge.weight as u32, 3);
+                    }else{
+                        unsigned.add_weighted_directed_edge(edge.from, edge.to, edge.weight as u32);
+                    }
+                }
+            }
+
+            let expected = graph.dinic(0, 11);
+
+            for flow in [unsigned.edmonds_karp(0, 11), unsigned.dinic(0, 11), unsigned.push_relabel(0, 11)]{
+                assert_eq!(i64::from(flow.value), expected.value);
+                assert_eq!(flow.source_side, expected.source_side);
+            }
+        }
+    }
+
+    #[test]
+    fn test_float_min_cost_flow(){
+        let mut graph: Graph<u32, f64> = Graph::new_weighted();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_costed_directed_edge(zeroth_node, first_node, 1.5, 0.5);
+        graph.add_costed_directed_edge(first_node, second_node, 1.5, 0.5);
+        graph.add_costed_directed_edge(zeroth_node, second_node, 1.0, 2.5);
+
+        let flow = graph.min_cost_max_flow(zeroth_node, second_node).unwrap();
+
+        assert_eq!(flow.value, 2.5);
+        assert_eq!(flow.cost, 4.0);
+
+        let flow = graph.min_cost_flow(zeroth_node, second_node, 0.5).unwrap();
+
+        assert_eq!(flow.value, 0.5);
+        assert_eq!(flow.cost, 0.5);
+    }
+
+    #[test]
+    fn test_labelled_capacities(){
+        use graph::Weighted;
+
+        // A pipe with a name and a capacity
+        struct Pipe{
+            name: &'static str,
+            capacity: i64
+        }
+
+        impl Weighted for Pipe{
+            type Weight = i64;
+
+            fn weight(&self) -> i64{
+                self.capacity
+            }
+        }
+
+        let mut graph = Graph::new_weighted();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_weighted_directed_edge(zeroth_node, first_node, Pipe{name: "main", capacity: 4});
+        graph.add_weighted_directed_edge(first_node, second_node, Pipe{name: "branch", capacity: 3});
+        graph.add_costed_directed_edge(zeroth_node, second_node, Pipe{name: "bypass", capacity: 2}, Pipe{name: "toll", capacity: 5});
+
+        assert_eq!(graph.dinic(zeroth_node, second_node).value, 5);
+
+        let flow = graph.min_cost_max_flow(zeroth_node, second_node).unwrap();
+
+        assert_eq!(flow.value, 5);
+        assert_eq!(flow.cost, 10);
+        assert_eq!(graph.edges(zeroth_node).map(|edge| edge.weight().name).collect::<Vec<&str>>(), vec!["main", "bypass"]);
+    }
+}
@@ -0,0 +1,919 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt;
+
+use graph::{Graph, Undirected};
+use union_find::UnionFind;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A split of the nodes of a graph into two sides, where every edge joins the two sides
+pub struct Bipartition{
+    /// The sorted IDs of the nodes on the first side, which holds the smallest ID of each
+    /// connected component
+    pub left: Vec<u32>,
+    /// The sorted IDs of the nodes on the second side
+    pub right: Vec<u32>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Error returned when a graph is not bipartite because it contains a cycle of odd length
+pub struct OddCycle{
+    /// The IDs of the nodes on the cycle, in the order the edges are followed.
+    /// The last node has an edge back to the first.
+    pub cycle: Vec<u32>
+}
+
+impl fmt::Display for OddCycle{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "graph is not bipartite, odd cycle through nodes {:?}", self.cycle)
+    }
+}
+
+impl Error for OddCycle{}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A set of edges where no two edges share a node
+pub struct Matching{
+    /// The matched pairs of node IDs, sorted
+    pub pairs: Vec<(u32, u32)>
+}
+
+impl Matching{
+
+    /// Returns the number of matched pairs
+    pub fn len(&self) -> usize{
+        self.pairs.len()
+    }
+
+    /// Returns true if nothing is matched
+    pub fn is_empty(&self) -> bool{
+        self.pairs.is_empty()
+    }
+
+    /// Returns the node matched with `node`, or `None` if it is unmatched
+    /// # Arguments
+    ///
+    /// `node` - The ID of the node to find the partner of
+    pub fn mate(&self, node: u32) -> Option<u32>{
+        self.pairs.iter().find_map(|&(a, b)| {
+            if a == node{
+                Some(b)
+            }else if b == node{
+                Some(a)
+            }else{
+                None
+            }
+        })
+    }
+}
+
+impl<T, E> Graph<T, E, Undirected>{
+
+    /// Check whether the nodes can be split into two sides with every edge joining the two
+    /// sides.  Returns the two sides, or an `OddCycle` error holding a cycle of odd length which
+    /// proves there is no such split.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_undirected_edge(node1, node2);
+    /// graph.add_undirected_edge(node2, node3);
+    ///
+    /// let sides = graph.is_bipartite().unwrap();
+    ///
+    /// assert_eq!(sides.left, vec![node1, node3]);
+    /// assert_eq!(sides.right, vec![node2]);
+    ///
+    /// // A triangle can not be split
+    /// graph.add_undirected_edge(node3, node1);
+    ///
+    /// assert_eq!(graph.is_bipartite().unwrap_err().cycle.len(), 3);
+    /// ```
+    pub fn is_bipartite(&self) -> Result<Bipartition, OddCycle>{
+        let neighbours = self.undirected_neighbours();
+
+        // Breadth first search gives each node a depth, and the sides are the even and odd depths
+        let mut depths: HashMap<u32, usize> = HashMap::new();
+        let mut parents: HashMap<u32, u32> = HashMap::new();
+
+        for root in self.sorted_node_ids(){
+            if depths.contains_key(&root){
+                continue;
+            }
+
+            depths.insert(root, 0);
+            let mut queue = VecDeque::new();
+            queue.push_back(root);
+
+            while let Some(node_id) = queue.pop_front(){
+                for &neighbour in &neighbours[&node_id]{
+                    match depths.get(&neighbour).cloned(){
+                        None => {
+                            depths.insert(neighbour, depths[&node_id] + 1);
+                            parents.insert(neighbour, node_id);
+                            queue.push_back(neighbour);
+                        },
+                        Some(depth) if depth % 2 == depths[&node_id] % 2 => {
+                            return Err(OddCycle{cycle: odd_cycle(&depths, &parents, node_id, neighbour)});
+                        },
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+
+        let mut sides = Bipartition{left: Vec::new(), right: Vec::new()};
+        for node_id in self.sorted_node_ids(){
+            if depths[&node_id].is_multiple_of(2){
+                sides.left.push(node_id);
+            }else{
+                sides.right.push(node_id);
+            }
+        }
+
+        Ok(sides)
+    }
+
+    /// Find a maximum cardinality matching of a bipartite graph using the Hopcroft-Karp
+    /// algorithm.  The sides are the ones found by `is_bipartite`.  Each pair is given as
+    /// `(left node, right node)`.  Runs in O(m sqrt(n)) time.  Returns an `OddCycle` error if
+    /// the graph is not bipartite.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph of workers and the shifts they can take
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let alice = graph.add_node("alice");
+    /// let bob = graph.add_node("bob");
+    /// let morning = graph.add_node("morning");
+    /// let evening = graph.add_node("evening");
+    ///
+    /// graph.add_undirected_edge(alice, morning);
+    /// graph.add_undirected_edge(alice, evening);
+    /// graph.add_undirected_edge(bob, morning);
+    ///
+    /// let matching = graph.hopcroft_karp().unwrap();
+    ///
+    /// assert_eq!(matching.pairs, vec![(alice, evening), (bob, morning)]);
+    /// ```
+    pub fn hopcroft_karp(&self) -> Result<Matching, OddCycle>{
+        let sides = self.is_bipartite()?;
+        let neighbours = self.undirected_neighbours();
+
+        let right_indices: HashMap<u32, usize> = sides.right.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+        let adjacency: Vec<Vec<usize>> = sides.left.iter()
+            .map(|node_id| neighbours[node_id].iter().map(|neighbour| right_indices[neighbour]).collect())
+            .collect();
+
+        let mut search = HopcroftKarp{
+            adjacency,
+            left_mates: vec![None; sides.left.len()],
+            right_mates: vec![None; sides.right.len()],
+            layers: vec![None; sides.left.len()],
+            limit: None
+        };
+
+        // Each phase augments along a maximal set of shortest disjoint augmenting paths
+        while search.build_layers(){
+            for left in 0..sides.left.len(){
+                if search.left_mates[left].is_none(){
+                    search.augment(left);
+                }
+            }
+        }
+
+        let pairs = search.left_mates.iter().enumerate()
+            .filter_map(|(left, right)| right.map(|right| (sides.left[left], sides.right[right])))
+            .collect();
+
+        Ok(Matching{pairs})
+    }
+
+    /// Find a maximum cardinality matching of any graph using Edmonds' blossom algorithm.
+    /// Self loops are ignored.  Each pair is given with the smaller node ID first.  Runs in
+    /// O(n^3) time.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    /// let node4 = graph.add_node(4);
+    ///
+    /// // A triangle with a tail, which is not bipartite
+    /// graph.add_undirected_edge(node1, node2);
+    /// graph.add_undirected_edge(node2, node3);
+    /// graph.add_undirected_edge(node3, node1);
+    /// graph.add_undirected_edge(node3, node4);
+    ///
+    /// let matching = graph.maximum_matching();
+    ///
+    /// assert_eq!(matching.pairs, vec![(node1, node2), (node3, node4)]);
+    /// assert!(graph.is_maximum_matching(&matching));
+    /// ```
+    pub fn maximum_matching(&self) -> Matching{
+        let node_ids = self.sorted_node_ids();
+        let neighbours = self.undirected_neighbours();
+
+        let indices: HashMap<u32, usize> = node_ids.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+        let adjacency: Vec<Vec<usize>> = node_ids.iter()
+            .map(|node_id| neighbours[node_id].iter().map(|neighbour| indices[neighbour]).collect())
+            .collect();
+
+        let mut search = Blossom{
+            mates: vec![None; node_ids.len()],
+            parents: vec![None; node_ids.len()],
+            bases: (0..node_ids.len()).collect(),
+            adjacency
+        };
+
+        for root in 0..node_ids.len(){
+            if search.mates[root].is_some(){
+                continue;
+            }
+
+            // Flip the matching along the augmenting path back to the root
+            let mut end = search.augmenting_path(root);
+            while let Some(node) = end{
+                let parent = search.parents[node].unwrap();
+                end = search.mates[parent];
+                search.mates[node] = Some(parent);
+                search.mates[parent] = Some(node);
+            }
+        }
+
+        let pairs = search.mates.iter().enumerate()
+            .filter_map(|(node, mate)| mate.filter(|&mate| node < mate).map(|mate| (node_ids[node], node_ids[mate])))
+            .collect();
+
+        Matching{pairs}
+    }
+
+    /// Check that every pair in `matching` is an edge of the graph between two different nodes,
+    /// and that no node is in more than one pair.
+    ///
+    /// # Arguments
+    ///
+    /// `matching` - The matching to check
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// # use kyles_algorithm_crate::graph::matching::Matching;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_undirected_edge(node1, node2);
+    /// graph.add_undirected_edge(node2, node3);
+    ///
+    /// assert!(graph.is_matching(&Matching{pairs: vec![(node1, node2)]}));
+    /// assert!(!graph.is_matching(&Matching{pairs: vec![(node1, node2), (node2, node3)]}));
+    /// assert!(!graph.is_matching(&Matching{pairs: vec![(node1, node3)]}));
+    /// ```
+    pub fn is_matching(&self, matching: &Matching) -> bool{
+        let neighbours = self.undirected_neighbours();
+        let mut used = HashSet::new();
+
+        matching.pairs.iter().all(|&(a, b)| {
+            a != b && neighbours.get(&a).is_some_and(|list| list.binary_search(&b).is_ok()) && used.insert(a) && used.insert(b)
+        })
+    }
+
+    /// Compute the size of a maximum matching using the Tutte-Berge formula, which is the
+    /// smallest value of `(n + |U| - odd(G - U)) / 2` over every set of nodes `U`, where
+    /// `odd(G - U)` is the number of connected components with an odd number of nodes left
+    /// after removing `U`.  Every set is tried, so this takes O(2^n m) time and is only meant
+    /// for checking results on small graphs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph has more than 24 nodes.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let centre = graph.add_node(0);
+    /// for i in 1..4{
+    ///     let leaf = graph.add_node(i);
+    ///     graph.add_undirected_edge(centre, leaf);
+    /// }
+    ///
+    /// // Removing the centre of a star leaves three odd components
+    /// assert_eq!(graph.tutte_berge_bound(), 1);
+    /// ```
+    pub fn tutte_berge_bound(&self) -> usize{
+        let node_ids = self.sorted_node_ids();
+        assert!(node_ids.len() <= 24, "tutte_berge_bound only supports graphs with at most 24 nodes");
+
+        let indices: HashMap<u32, usize> = node_ids.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+        let mut edges = Vec::new();
+        for node_id in self.sorted_node_ids(){
+            for edge in self.edges(node_id){
+                if let Some(&to) = indices.get(&edge.to){
+                    edges.push((indices[&edge.from], to));
+                }
+            }
+        }
+
+        let size = node_ids.len();
+        let mut bound = size / 2;
+
+        for removed in 0..1u32 << size{
+            let mut sets = UnionFind::new(size);
+            for &(a, b) in &edges{
+                if removed & (1 << a) == 0 && removed & (1 << b) == 0{
+                    sets.union(a, b);
+                }
+            }
+
+            let mut component_sizes = vec![0; size];
+            for node in 0..size{
+                if removed & (1 << node) == 0{
+                    component_sizes[sets.find(node)] += 1;
+                }
+            }
+
+            let odd = component_sizes.iter().filter(|&&count| count % 2 == 1).count();
+            let removed_count = removed.count_ones() as usize;
+            if odd > removed_count{
+                bound = bound.min((size + removed_count - odd) / 2);
+            }
+        }
+
+        bound
+    }
+
+    /// Check that `matching` is a valid matching of the graph and that no matching is larger,
+    /// using `tutte_berge_bound`.  Only meant for small graphs.
+    ///
+    /// # Arguments
+    ///
+    /// `matching` - The matching to check
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph has more than 24 nodes.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// # use kyles_algorithm_crate::graph::matching::Matching;
+    /// // Create a path of four nodes
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    /// let node4 = graph.add_node(4);
+    ///
+    /// graph.add_undirected_edge(node1, node2);
+    /// graph.add_undirected_edge(node2, node3);
+    /// graph.add_undirected_edge(node3, node4);
+    ///
+    /// assert!(!graph.is_maximum_matching(&Matching{pairs: vec![(node2, node3)]}));
+    /// assert!(graph.is_maximum_matching(&Matching{pairs: vec![(node1, node2), (node3, node4)]}));
+    /// ```
+    pub fn is_maximum_matching(&self, matching: &Matching) -> bool{
+        self.is_matching(matching) && matching.len() == self.tutte_berge_bound()
+    }
+
+    /// Returns the sorted, distinct neighbours of every node, leaving out parallel edges
+    pub(crate) fn undirected_neighbours(&self) -> HashMap<u32, Vec<u32>>{
+        self.sorted_node_ids().into_iter().map(|node_id| {
+            let mut list: Vec<u32> = self.neighbors(node_id)
+                .filter(|&to| self.contains_node(to))
+                .collect();
+            list.sort();
+            list.dedup();
+
+            (node_id, list)
+        }).collect()
+    }
+}
+
+/// Join the tree paths from two nodes at the same depth parity up to where they meet, which
+/// together with the edge between the nodes makes a cycle of odd length
+fn odd_cycle(depths: &HashMap<u32, usize>, parents: &HashMap<u32, u32>, a: u32, b: u32) -> Vec<u32>{
+    let mut a_path = vec![a];
+    let mut b_path = vec![b];
+    let (mut a_top, mut b_top) = (a, b);
+
+    while a_top != b_top{
+        if depths[&a_top] >= depths[&b_top]{
+            a_top = parents[&a_top];
+            a_path.push(a_top);
+        }else{
+            b_top = parents[&b_top];
+            b_path.push(b_top);
+        }
+    }
+
+    // Go down from the meeting point to `a`, cross to `b` and climb back up
+    b_path.pop();
+    a_path.reverse();
+    a_path.extend(b_path);
+    a_path
+}
+
+/// State of the Hopcroft-Karp search, with each side indexed by position in sorted order
+struct HopcroftKarp{
+    adjacency: Vec<Vec<usize>>,
+    left_mates: Vec<Option<usize>>,
+    right_mates: Vec<Option<usize>>,
+    // The layer of each left node in the alternating breadth first search
+    layers: Vec<Option<usize>>,
+    // The layer of the left nodes that end the shortest augmenting paths of this phase
+    limit: Option<usize>
+}
+
+impl HopcroftKarp{
+
+    /// Layer the left nodes by alternating path distance from an unmatched left node.
+    /// Returns true if an augmenting path exists.
+    fn build_layers(&mut self) -> bool{
+        let mut queue = VecDeque::new();
+
+        for left in 0..self.left_mates.len(){
+            if self.left_mates[left].is_none(){
+                self.layers[left] = Some(0);
+                queue.push_back(left);
+            }else{
+                self.layers[left] = None;
+            }
+        }
+
+        self.limit = None;
+        while let Some(left) = queue.pop_front(){
+            let layer = self.layers[left].unwrap();
+
+            // Only the shortest augmenting paths are used, so stop past the first free right node
+            if self.limit.is_some_and(|limit| layer >= limit){
+                continue;
+            }
+
+            for &right in &self.adjacency[left]{
+                match self.right_mates[right]{
+                    None => self.limit = Some(layer),
+                    Some(mate) => {
+                        if self.layers[mate].is_none(){
+                            self.layers[mate] = Some(layer + 1);
+                            queue.push_back(mate);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.limit.is_some()
+    }
+
+    /// Look for an augmenting path from `start` that follows the layers, and flip it if found
+    fn augment(&mut self, start: usize) -> bool{
+        // Each frame is a left node on the path and the index of the edge it is trying
+        let mut stack = vec![(start, 0)];
+
+        while let Some(&mut (left, ref mut next_edge)) = stack.last_mut(){
+            if *next_edge == self.adjacency[left].len(){
+                // Nothing useful is reachable through this node for the rest of the phase
+                self.layers[left] = None;
+                stack.pop();
+
+                if let Some((_, parent_edge)) = stack.last_mut(){
+                    *parent_edge += 1;
+                }
+                continue;
+            }
+
+            let right = self.adjacency[left][*next_edge];
+            let next = self.layers[left].map(|layer| layer + 1);
+
+            match self.right_mates[right]{
+                None if self.layers[left] == self.limit => {
+                    // Every left node on the stack takes the right node its edge goes to
+                    for &(left, edge) in &stack{
+                        let right = self.adjacency[left][edge];
+                        self.left_mates[left] = Some(right);
+                        self.right_mates[right] = Some(left);
+                    }
+                    return true;
+                },
+                Some(mate) if next <= self.limit && self.layers[mate] == next => stack.push((mate, 0)),
+                _ => *next_edge += 1
+            }
+        }
+
+        false
+    }
+}
+
+/// State of Edmonds' blossom algorithm, with nodes indexed by position in sorted order
+struct Blossom{
+    adjacency: Vec<Vec<usize>>,
+    mates: Vec<Option<usize>>,
+    // The node each odd node was reached from in the current search tree
+    parents: Vec<Option<usize>>,
+    // The base of the blossom each node has been shrunk into, or the node itself
+    bases: Vec<usize>
+}
+
+impl Blossom{
+
+    /// Grow an alternating tree from the unmatched node `root`, shrinking blossoms as they
+    /// are found.  Returns the unmatched node at the end of an augmenting path, if there is one.
+    fn augmenting_path(&mut self, root: usize) -> Option<usize>{
+        let size = self.adjacency.len();
+
+        // Even nodes are the root and every matched node reached through its mate
+        let mut even = vec![false; size];
+        self.parents = vec![None; size];
+        self.bases = (0..size).collect();
+
+        even[root] = true;
+        let mut queue = VecDeque::new();
+        queue.push_back(root);
+
+        while let Some(node) = queue.pop_front(){
+            for i in 0..self.adjacency[node].len(){
+                let to = self.adjacency[node][i];
+
+                if self.bases[node] == self.bases[to] || self.mates[node] == Some(to){
+                    continue;
+                }
+
+                if to == root || self.mates[to].is_some_and(|mate| self.parents[mate].is_some()){
+                    // Two even nodes are joined, which closes an odd cycle to shrink
+                    let base = self.common_base(node, to);
+                    let mut in_blossom = vec![false; size];
+                    self.mark_path(node, base, to, &mut in_blossom);
+                    self.mark_path(to, base, node, &mut in_blossom);
+
+                    for member in 0..size{
+                        if in_blossom[self.bases[member]]{
+                            self.bases[member] = base;
+                            if !even[member]{
+                                even[member] = true;
+                                queue.push_back(member);
+                            }
+                        }
+                    }
+                }else if self.parents[to].is_none(){
+                    self.parents[to] = Some(node);
+
+                    match self.mates[to]{
+                        None => return Some(to),
+                        Some(mate) => {
+                            even[mate] = true;
+                            queue.push_back(mate);
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Find the base of the smallest blossom containing both `a` and `b` by walking up the
+    /// alternating tree from each
+    fn common_base(&self, a: usize, b: usize) -> usize{
+        let mut on_path = vec![false; self.adjacency.len()];
+
+        let mut node = a;
+        loop{
+            node = self.bases[node];
+            on_path[node] = true;
+
+            match self.mates[node]{
+                Some(mate) => node = self.parents[mate].unwrap(),
+                None => break
+            }
+        }
+
+        let mut node = b;
+        loop{
+            node = self.bases[node];
+            if on_path[node]{
+                return node;
+            }
+            node = self.parents[self.mates[node].unwrap()].unwrap();
+        }
+    }
+
+    /// Mark the blossoms on the tree path from `node` up to `base`, pointing the odd nodes
+    /// along it the other way round the cycle so paths through the blossom can be followed
+    fn mark_path(&mut self, mut node: usize, base: usize, mut child: usize, in_blossom: &mut [bool]){
+        while self.bases[node] != base{
+            let mate = self.mates[node].unwrap();
+            in_blossom[self.bases[node]] = true;
+            in_blossom[self.bases[mate]] = true;
+            self.parents[node] = Some(child);
+            child = mate;
+            node = self.parents[mate].unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_is_bipartite(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+
+        graph.add_undirected_edge(zeroth_node, first_node);
+        graph.add_undirected_edge(first_node, second_node);
+        graph.add_undirected_edge(second_node, third_node);
+        graph.add_undirected_edge(third_node, zeroth_node);
+
+        let sides = graph.is_bipartite().unwrap();
+
+        assert_eq!(sides.left, vec![zeroth_node, second_node, fourth_node]);
+        assert_eq!(sides.right, vec![first_node, third_node]);
+
+        // The order of the ends of an edge does not matter
+        let mut graph2 = Graph::new_undirected();
+
+        let node1_id = graph2.add_node(1);
+        let node2_id = graph2.add_node(2);
+        let node3_id = graph2.add_node(3);
+
+        graph2.add_undirected_edge(node2_id, node1_id);
+        graph2.add_undirected_edge(node3_id, node1_id);
+
+        let sides = graph2.is_bipartite().unwrap();
+
+        assert_eq!(sides.left, vec![node1_id]);
+        assert_eq!(sides.right, vec![node2_id, node3_id]);
+    }
+
+    #[test]
+    fn test_is_bipartite_odd_cycle(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+
+        graph.add_undirected_edge(zeroth_node, first_node);
+        graph.add_undirected_edge(first_node, second_node);
+        graph.add_undirected_edge(second_node, third_node);
+        graph.add_undirected_edge(third_node, fourth_node);
+        graph.add_undirected_edge(fourth_node, zeroth_node);
+
+        let cycle = graph.is_bipartite().unwrap_err().cycle;
+
+        assert_eq!(cycle.len(), 5);
+        assert_eq!(cycle[0], zeroth_node);
+
+        // Consecutive nodes on the cycle are joined by edges
+        for i in 0..cycle.len(){
+            let (a, b) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+            assert!(graph.neighbors(a).any(|to| to == b));
+        }
+
+        // Self loops are odd cycles
+        let mut graph2 = Graph::new_undirected();
+
+        let node1_id = graph2.add_node(1);
+        graph2.add_undirected_edge(node1_id, node1_id);
+
+        assert_eq!(graph2.is_bipartite().unwrap_err().cycle, vec![node1_id]);
+    }
+
+    #[test]
+    fn test_hopcroft_karp(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let workers: Vec<u32> = (0..4).map(|i| graph.add_node(i)).collect();
+        let shifts: Vec<u32> = (4..8).map(|i| graph.add_node(i)).collect();
+
+        graph.add_undirected_edge(workers[0], shifts[0]);
+        graph.add_undirected_edge(workers[0], shifts[1]);
+        graph.add_undirected_edge(workers[1], shifts[0]);
+        graph.add_undirected_edge(workers[2], shifts[1]);
+        graph.add_undirected_edge(workers[2], shifts[2]);
+        graph.add_undirected_edge(workers[3], shifts[2]);
+
+        let matching = graph.hopcroft_karp().unwrap();
+
+        assert_eq!(matching.len(), 3);
+
+        // Every pair is an edge and no node is used twice
+        let mut used = Vec::new();
+        for &(a, b) in &matching.pairs{
+            assert!(graph.neighbors(a).any(|to| to == b));
+            used.push(a);
+            used.push(b);
+        }
+        used.sort();
+        used.dedup();
+        assert_eq!(used.len(), 6);
+
+        for &(a, b) in &matching.pairs{
+            assert_eq!(matching.mate(a), Some(b));
+            assert_eq!(matching.mate(b), Some(a));
+        }
+        assert_eq!(matching.mate(shifts[3]), None);
+
+        // The matching size agrees with a unit capacity max flow from workers to shifts
+        let mut flow_graph = Graph::new();
+        for i in 0..10{
+            flow_graph.add_node(i);
+        }
+        let (source, sink) = (8, 9);
+
+        for &worker in &workers{
+            flow_graph.add_directed_edge(source, worker);
+            for edge in graph.edges(worker){
+                flow_graph.add_directed_edge(worker, edge.to);
+            }
+        }
+        for &shift in &shifts{
+            flow_graph.add_directed_edge(shift, sink);
+        }
+
+        assert_eq!(flow_graph.dinic(source, sink).value, 3);
+    }
+
+    #[test]
+    fn test_hopcroft_karp_long_path(){
+        use graph::Graph;
+
+        // The greedy first phase leaves a single augmenting path through every node, which
+        // would overflow the stack if augmenting recursed once per matched edge
+        let size = 100_000;
+        let mut graph = Graph::new_undirected();
+
+        let left: Vec<u32> = (0..size).map(|i| graph.add_node(i)).collect();
+        let mut right: Vec<u32> = (0..size).map(|i| graph.add_node(size + i)).collect();
+        right.reverse();
+
+        for i in 0..size as usize - 1{
+            graph.add_undirected_edge(left[i], right[i]);
+            graph.add_undirected_edge(left[i], right[i + 1]);
+        }
+        graph.add_undirected_edge(left[size as usize - 1], right[size as usize - 1]);
+
+        let matching = graph.hopcroft_karp().unwrap();
+
+        assert_eq!(matching.len(), size as usize);
+        assert_eq!(matching.mate(left[0]), Some(right[0]));
+    }
+
+    #[test]
+    fn test_hopcroft_karp_not_bipartite(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_undirected_edge(zeroth_node, first_node);
+        graph.add_undirected_edge(first_node, second_node);
+        graph.add_undirected_edge(second_node, zeroth_node);
+
+        assert!(graph.hopcroft_karp().is_err());
+
+        // Graphs without edges have empty matchings
+        let mut graph2 = Graph::new_undirected();
+        graph2.add_node(1);
+
+        assert!(graph2.hopcroft_karp().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_maximum_matching(){
+        use graph::Graph;
+
+        // The Petersen graph has a perfect matching but is not bipartite
+        let mut graph = Graph::new_undirected();
+
+        let nodes: Vec<u32> = (0..10).map(|i| graph.add_node(i)).collect();
+        for i in 0..5{
+            graph.add_undirected_edge(nodes[i], nodes[(i + 1) % 5]);
+            graph.add_undirected_edge(nodes[i], nodes[i + 5]);
+            graph.add_undirected_edge(nodes[i + 5], nodes[(i + 2) % 5 + 5]);
+        }
+
+        let matching = graph.maximum_matching();
+
+        assert_eq!(matching.len(), 5);
+        assert!(graph.is_matching(&matching));
+        assert!(graph.is_maximum_matching(&matching));
+
+        // An odd cycle hanging off a path, where the search has to shrink the cycle
+        let mut graph2 = Graph::new_undirected();
+
+        let zeroth_node = graph2.add_node(0);
+        let first_node = graph2.add_node(1);
+        let second_node = graph2.add_node(2);
+        let third_node = graph2.add_node(3);
+        let fourth_node = graph2.add_node(4);
+        let fifth_node = graph2.add_node(5);
+        let sixth_node = graph2.add_node(6);
+
+        graph2.add_undirected_edge(zeroth_node, first_node);
+        graph2.add_undirected_edge(first_node, second_node);
+        graph2.add_undirected_edge(second_node, third_node);
+        graph2.add_undirected_edge(third_node, fourth_node);
+        graph2.add_undirected_edge(fourth_node, fifth_node);
+        graph2.add_undirected_edge(fifth_node, first_node);
+        graph2.add_undirected_edge(third_node, sixth_node);
+
+        let matching = graph2.maximum_matching();
+
+        assert_eq!(matching.len(), 3);
+        assert_eq!(matching.mate(zeroth_node), Some(first_node));
+        assert!(graph2.is_maximum_matching(&matching));
+    }
+
+    #[test]
+    fn test_maximum_matching_random(){
+        use graph::Graph;
+        use graph::test_util::Lcg;
+
+        let mut random = Lcg::new(3);
+        let mut next = |bound: u32| random.below(u64::from(bound)) as u32;
+
+        for round in 0..40{
+            let mut graph = Graph::new_undirected();
+
+            let size = 1 + next(12);
+            for i in 0..size{
+                graph.add_node(i);
+            }
+            for _ in 0..next(3 * size){
+                graph.add_undirected_edge(next(size), next(size));
+            }
+
+            let matching = graph.maximum_matching();
+
+            assert!(graph.is_matching(&matching), "round {}", round);
+            assert_eq!(matching.len(), graph.tutte_berge_bound(), "round {}", round);
+
+            // Blossoms are not needed on bipartite graphs, and both algorithms agree there
+            if let Ok(bipartite_matching) = graph.hopcroft_karp(){
+                assert_eq!(matching.len(), bipartite_matching.len(), "round {}", round);
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_matching(){
+        use graph::Graph;
+        use graph::matching::Matching;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_undirected_edge(first_node, zeroth_node);
+        graph.add_undirected_edge(second_node, second_node);
+
+        assert!(graph.is_matching(&Matching{pairs: vec![]}));
+        assert!(graph.is_matching(&Matching{pairs: vec![(zeroth_node, first_node)]}));
+        assert!(!graph.is_matching(&Matching{pairs: vec![(second_node, second_node)]}));
+        assert!(!graph.is_matching(&Matching{pairs: vec![(zeroth_node, first_node), (first_node, zeroth_node)]}));
+        assert!(!graph.is_matching(&Matching{pairs: vec![(zeroth_node, 7)]}));
+
+        assert_eq!(graph.tutte_berge_bound(), 1);
+    }
+}
@@ -0,0 +1,621 @@
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::Index;
+
+use graph::{Adjacency, Directed, Direction, Undirected, Weight, Weighted};
+use graph::all_pairs::{self, AllPairsShortestPaths, TransitiveClosure};
+use graph::shortest_path::NegativeCycle;
+
+/// Adjacency matrix implementation of a graph for small, dense graphs, with nodes storing data
+/// of type `T` and edges weighted by values of type `E`.  Looking up the edge between two nodes
+/// takes O(1) time, but the matrix takes O(n^2) space.  Nodes are numbered from 0 in the order
+/// they are added and can not be removed.  There is at most one edge from one node to another,
+/// so adding a second one replaces the first.  Cells without an edge hold `E::default()`.
+pub struct MatrixGraph<T, E = i64, D = Directed>{
+    data: Vec<T>,
+    // weights[from * stride + to] is the weight of the edge from one node to another, and bit
+    // from * stride + to of present says whether that edge exists.  The stride grows by half
+    // again when it runs out, so adding nodes does not copy the matrix every time.
+    weights: Vec<E>,
+    present: Vec<u64>,
+    stride: usize,
+    direction: PhantomData<D>
+}
+
+impl<T> MatrixGraph<T>{
+
+    /// Returns a new instance of a directed MatrixGraph with `i64` edge weights
+    /// # Example
+    ///
+    /// ```
+    /// # use kyles_algorithm_crate::graph::matrix::MatrixGraph;
+    /// // Creates a new graph
+    /// let graph: MatrixGraph<u32> = MatrixGraph::new();
+    /// ```
+    pub fn new() -> Self{
+        MatrixGraph::new_weighted()
+    }
+}
+
+impl<T> MatrixGraph<T, i64, Undirected>{
+
+    /// Returns a new instance of an undirected MatrixGraph with `i64` edge weights
+    /// # Example
+    ///
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Undirected;
+    /// # use kyles_algorithm_crate::graph::matrix::MatrixGraph;
+    /// // Creates a new undirected graph
+    /// let graph: MatrixGraph<u32, i64, Undirected> = MatrixGraph::new_undirected();
+    /// ```
+    pub fn new_undirected() -> Self{
+        MatrixGraph::new_weighted()
+    }
+}
+
+impl<T, E, D: Direction> MatrixGraph<T, E, D>{
+
+    /// Returns a new instance of a MatrixGraph with edge weights of any type, either directed
+    /// or undirected
+    /// # Example
+    ///
+    /// ```
+    /// # use kyles_algorithm_crate::graph::matrix::MatrixGraph;
+    /// // Creates a new graph with floating point weights
+    /// let graph: MatrixGraph<u32, f64> = MatrixGraph::new_weighted();
+    /// ```
+    pub fn new_weighted() -> Self{
+        MatrixGraph{data: Vec::new(), weights: Vec::new(), present: Vec::new(), stride: 0, direction: PhantomData}
+    }
+
+    /// Returns a new instance of a MatrixGraph with room for `capacity` nodes, so that adding
+    /// up to that many nodes never moves the matrix
+    /// # Arguments
+    ///
+    /// `capacity` - The number of nodes to make room for
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::matrix::MatrixGraph;
+    /// // Creates a new graph with room for 100 nodes
+    /// let mut graph: MatrixGraph<u32> = MatrixGraph::with_capacity(100);
+    ///
+    /// let node1 = graph.add_node(1);
+    ///
+    /// assert_eq!(graph.node_count(), 1);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self where E: Default{
+        let mut graph = MatrixGraph::new_weighted();
+        graph.resize(capacity);
+        graph
+    }
+
+    /// Add a new node to the graph with supplied data.  Returns the ID of the node, which is
+    /// the number of nodes added before it.
+    /// # Arguments
+    ///
+    /// `data` - The data to put in the node to be added
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::matrix::MatrixGraph;
+    /// // Create a graph
+    /// let mut graph = MatrixGraph::new();
+    ///
+    /// let node1 = graph.add_node(10);
+    /// let node2 = graph.add_node(20);
+    ///
+    /// assert_eq!((node1, node2), (0, 1));
+    /// assert_eq!(graph[node2], 20);
+    /// ```
+    pub fn add_node(&mut self, data: T) -> u32 where E: Default{
+        let node_id = self.data.len();
+
+        // Move the rows into a bigger matrix when there is no room for another column
+        if node_id == self.stride{
+            self.resize((self.stride + self.stride / 2).max(4));
+        }
+
+        self.data.push(data);
+        node_id as u32
+    }
+
+    /// Returns the number of nodes in the graph
+    pub fn node_count(&self) -> usize{
+        self.data.len()
+    }
+
+    /// Returns the number of edges in the graph.  An undirected edge counts once.
+    pub fn edge_count(&self) -> usize{
+        let n = self.data.len();
+
+        // An undirected edge is in both halves of the matrix, so only count the upper half
+        (0..n).map(|from| {
+            let first = if D::is_directed(){ 0 }else{ from };
+            (first..n).filter(|&to| self.is_present(from * self.stride + to)).count()
+        }).sum()
+    }
+
+    /// Get the weight of the edge from one node to another, or `None` if there is no such edge
+    /// # Arguments
+    ///
+    /// `from` - The ID of the node the edge starts at
+    /// `to` - The ID of the node the edge goes to
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::matrix::MatrixGraph;
+    /// // Create a graph
+    /// let mut graph = MatrixGraph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// graph.add_weighted_undirected_edge(node1, node2, 5);
+    ///
+    /// assert_eq!(graph.edge_weight(node2, node1), Some(&5));
+    /// assert_eq!(graph.edge_weight(node1, node1), None);
+    /// ```
+    pub fn edge_weight(&self, from: u32, to: u32) -> Option<&E>{
+        let cell = self.cell(from, to)?;
+
+        if self.is_present(cell){
+            Some(&self.weights[cell])
+        }else{
+            None
+        }
+    }
+
+    /// Returns true if there is an edge from one node to another
+    /// # Arguments
+    ///
+    /// `from` - The ID of the node the edge starts at
+    /// `to` - The ID of the node the edge goes to
+    pub fn has_edge(&self, from: u32, to: u32) -> bool{
+        self.edge_weight(from, to).is_some()
+    }
+
+    /// Remove the edge from one node to another.  Returns the weight of the removed edge, or
+    /// `None` if there was no edge.  Removing an edge of an undirected graph removes it both
+    /// ways.
+    /// # Arguments
+    ///
+    /// `from` - The ID of the node the edge starts at
+    /// `to` - The ID of the node the edge goes to
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::matrix::MatrixGraph;
+    /// // Create a graph
+    /// let mut graph = MatrixGraph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// graph.add_weighted_directed_edge(node1, node2, 10);
+    ///
+    /// assert_eq!(graph.remove_edge(node1, node2), Some(10));
+    /// assert_eq!(graph.remove_edge(node1, node2), None);
+    /// ```
+    pub fn remove_edge(&mut self, from: u32, to: u32) -> Option<E> where E: Default{
+        let cell = self.cell(from, to)?;
+
+        if !self.is_present(cell){
+            return None;
+        }
+
+        if !D::is_directed(){
+            let back = self.cell(to, from)?;
+            self.set_present(back, false);
+            self.weights[back] = E::default();
+        }
+
+        self.set_present(cell, false);
+        Some(mem::take(&mut self.weights[cell]))
+    }
+
+    /// Returns an iterator over the IDs of the nodes the edges of a node go to, in ascending
+    /// order.  The iterator is empty if the node is not in the graph.
+    /// # Arguments
+    /// `node` - The ID of the node to get the neighbours of
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::matrix::MatrixGraph;
+    /// // Create a graph
+    /// let mut graph = MatrixGraph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node1, node3);
+    /// graph.add_directed_edge(node1, node2);
+    ///
+    /// assert_eq!(graph.neighbors(node1).collect::<Vec<u32>>(), vec![node2, node3]);
+    /// ```
+    pub fn neighbors(&self, node: u32) -> MatrixNeighbors<'_>{
+        let (start, len) = match self.cell(node, 0){
+            Some(start) => (start, self.data.len()),
+            None => (0, 0)
+        };
+
+        MatrixNeighbors{present: &self.present, start, len, next: 0}
+    }
+
+    /// Find which nodes can reach which other nodes with Warshall's algorithm.  See
+    /// `all_pairs::transitive_closure`.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::matrix::MatrixGraph;
+    /// // Create a graph
+    /// let mut graph = MatrixGraph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node1, node2);
+    /// graph.add_directed_edge(node2, node3);
+    ///
+    /// let closure = graph.transitive_closure();
+    ///
+    /// assert!(closure.reaches(node1, node3));
+    /// assert!(!closure.reaches(node3, node2));
+    /// ```
+    pub fn transitive_closure(&self) -> TransitiveClosure{
+        all_pairs::transitive_closure(self)
+    }
+
+    /// Find the position of the cell for the edge from one node to another, or `None` if
+    /// either node is not in the graph
+    fn cell(&self, from: u32, to: u32) -> Option<usize>{
+        let (from, to) = (from as usize, to as usize);
+
+        if from < self.data.len() && to < self.data.len(){
+            Some(from * self.stride + to)
+        }else{
+            None
+        }
+    }
+
+    /// Set the weight of the edge from one node to another, if both nodes are in the graph
+    fn set_edge(&mut self, from: u32, to: u32, weight: E){
+        if let Some(cell) = self.cell(from, to){
+            self.weights[cell] = weight;
+            self.set_present(cell, true);
+        }
+    }
+
+    /// Returns true if the cell holds an edge
+    fn is_present(&self, cell: usize) -> bool{
+        self.present[cell / 64] & (1 << (cell % 64)) != 0
+    }
+
+    /// Mark the cell as holding an edge or not
+    fn set_present(&mut self, cell: usize, present: bool){
+        if present{
+            self.present[cell / 64] |= 1 << (cell % 64);
+        }else{
+            self.present[cell / 64] &= !(1 << (cell % 64));
+        }
+    }
+
+    /// Move the rows into a matrix with room for `stride` nodes
+    fn resize(&mut self, stride: usize) where E: Default{
+        let mut weights = Vec::with_capacity(stride * stride);
+        weights.resize_with(stride * stride, E::default);
+        let old_present = mem::replace(&mut self.present, vec![0; (stride * stride).div_ceil(64)]);
+
+        let n = self.data.len();
+        for from in 0..n{
+            for to in 0..n{
+                let old_cell = from * self.stride + to;
+                if old_present[old_cell / 64] & (1 << (old_cell % 64)) != 0{
+                    let cell = from * stride + to;
+                    weights[cell] = mem::take(&mut self.weights[old_cell]);
+                    self.present[cell / 64] |= 1 << (cell % 64);
+                }
+            }
+        }
+
+        self.weights = weights;
+        self.stride = stride;
+    }
+}
+
+impl<T, E> MatrixGraph<T, E, Directed>{
+
+    /// Add a directed edge from one node to another with a weight of one, replacing any edge
+    /// already there
+    /// # Arguments
+    ///
+    /// `from` - The ID of the node the edge starts at
+    /// `to` - The ID of the node the edge goes to
+    pub fn add_directed_edge(&mut self, from: u32, to: u32) where E: Weight{
+        self.set_edge(from, to, E::one());
+    }
+
+    /// Add a weighted directed edge from one node to another, replacing any edge already there
+    /// # Arguments
+    ///
+    /// `from` - The ID of the node the edge starts at
+    /// `to` - The ID of the node the edge goes to
+    /// `weight` - The weight of the edge
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::matrix::MatrixGraph;
+    /// // Create a graph
+    /// let mut graph = MatrixGraph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    ///
+    /// graph.add_weighted_directed_edge(node1, node2, 3);
+    /// graph.add_weighted_directed_edge(node1, node2, 8);
+    ///
+    /// assert_eq!(graph.edge_weight(node1, node2), Some(&8));
+    /// assert_eq!(graph.edge_weight(node2, node1), None);
+    /// ```
+    pub fn add_weighted_directed_edge(&mut self, from: u32, to: u32, weight: E){
+        self.set_edge(from, to, weight);
+    }
+
+    /// Find the shortest paths between every pair of nodes using the Floyd-Warshall algorithm.
+    /// See `all_pairs::floyd_warshall`.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::matrix::MatrixGraph;
+    /// // Create a graph
+    /// let mut graph = MatrixGraph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_weighted_directed_edge(node1, node2, 4);
+    /// graph.add_weighted_directed_edge(node2, node3, -2);
+    /// graph.add_weighted_directed_edge(node3, node1, 3);
+    ///
+    /// let paths = graph.floyd_warshall().unwrap();
+    ///
+    /// assert_eq!(paths.distance(node1, node3), Some(2));
+    /// assert_eq!(paths.path(node2, node1), Some(vec![node2, node3, node1]));
+    /// ```
+    pub fn floyd_warshall(&self) -> Result<AllPairsShortestPaths<E::Weight>, NegativeCycle> where E: Weighted{
+        all_pairs::floyd_warshall(self)
+    }
+}
+
+impl<T, E> MatrixGraph<T, E, Undirected>{
+
+    /// Add an undirected edge between two nodes with a weight of one, replacing any edge
+    /// already there
+    /// # Arguments
+    ///
+    /// `node1` - The ID of one end of the edge
+    /// `node2` - The ID of the other end of the edge
+    pub fn add_undirected_edge(&mut self, node1: u32, node2: u32) where E: Weight{
+        self.add_weighted_undirected_edge(node1, node2, E::one());
+    }
+
+    /// Add a weighted undirected edge between two nodes, replacing any edge already there
+    /// # Arguments
+    ///
+    /// `node1` - The ID of one end of the edge
+    /// `node2` - The ID of the other end of the edge
+    /// `weight` - The weight of the edge
+    pub fn add_weighted_undirected_edge(&mut self, node1: u32, node2: u32, weight: E) where E: Clone{
+        if self.cell(node1, node2).is_some(){
+            self.set_edge(node2, node1, weight.clone());
+            self.set_edge(node1, node2, weight);
+        }
+    }
+}
+
+impl<T, E, D: Direction> Default for MatrixGraph<T, E, D>{
+    fn default() -> Self{
+        MatrixGraph::new_weighted()
+    }
+}
+
+impl<T, E, D: Direction> Adjacency for MatrixGraph<T, E, D>{
+    type Payload = E;
+    type Direction = D;
+
+    fn node_ids(&self) -> Vec<u32>{
+        (0..self.data.len() as u32).collect()
+    }
+
+    fn for_each_edge<F: FnMut(u32, &E)>(&self, node: u32, mut visit: F){
+        if let Some(start) = self.cell(node, 0){
+            for to in 0..self.data.len(){
+                if self.is_present(start + to){
+                    visit(to as u32, &self.weights[start + to]);
+                }
+            }
+        }
+    }
+}
+
+impl<T, E, D> Index<u32> for MatrixGraph<T, E, D>{
+    type Output = T;
+
+    /// Returns the data of the node with the given ID
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in the graph
+    fn index(&self, node: u32) -> &T{
+        match self.data.get(node as usize){
+            Some(data) => data,
+            None => panic!("node {} is not in the graph", node)
+        }
+    }
+}
+
+/// Iterator over the IDs of the nodes the edges of a node go to, made by
+/// `MatrixGraph::neighbors`
+pub struct MatrixNeighbors<'a>{
+    present: &'a [u64],
+    start: usize,
+    len: usize,
+    next: usize
+}
+
+impl<'a> Iterator for MatrixNeighbors<'a>{
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32>{
+        while self.next < self.len{
+            let to = self.next;
+            let cell = self.start + to;
+            self.next += 1;
+
+            if self.present[cell / 64] & (1 << (cell % 64)) != 0{
+                return Some(to as u32);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use graph::{Graph, Undirected};
+    use graph::all_pairs::{floyd_warshall, transitive_closure};
+    use graph::matrix::MatrixGraph;
+    use graph::test_util::random_edges;
+
+    /// Build the same random graph as a matrix and as an adjacency list, without parallel edges
+    fn random_graphs(n: u32, m: u32, seed: u64) -> (MatrixGraph<u32>, Graph<u32>){
+        let mut matrix = MatrixGraph::new();
+        let mut list = Graph::new();
+        for i in 0..n{
+            matrix.add_node(i);
+            list.add_node(i);
+        }
+
+        for (from, to, weight) in random_edges(n, m, seed, -3..17){
+            if from != to && !matrix.has_edge(from, to){
+                matrix.add_weighted_directed_edge(from, to, weight);
+                list.add_weighted_directed_edge(from, to, weight);
+            }
+        }
+
+        (matrix, list)
+    }
+
+    #[test]
+    fn test_matrix_graph(){
+        let mut graph = MatrixGraph::new();
+
+        let zeroth_node = graph.add_node('A');
+        let first_node = graph.add_node('B');
+        let second_node = graph.add_node('C');
+
+        graph.add_weighted_directed_edge(zeroth_node, first_node, 3);
+        graph.add_weighted_directed_edge(zeroth_node, second_node, 4);
+        graph.add_directed_edge(second_node, second_node);
+
+        // Missing nodes are ignored
+        graph.add_directed_edge(zeroth_node, 7);
+
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph[first_node], 'B');
+        assert_eq!(graph.edge_weight(zeroth_node, second_node), Some(&4));
+        assert_eq!(graph.edge_weight(second_node, zeroth_node), None);
+        assert_eq!(graph.edge_weight(7, zeroth_node), None);
+        assert_eq!(graph.neighbors(zeroth_node).collect::<Vec<u32>>(), vec![first_node, second_node]);
+        assert_eq!(graph.neighbors(7).count(), 0);
+
+        assert_eq!(graph.remove_edge(zeroth_node, first_node), Some(3));
+        assert_eq!(graph.edge_count(), 2);
+
+        // Growing the matrix keeps the edges
+        for i in 3..20{
+            graph.add_node(char::from(b'A' + i as u8));
+        }
+        graph.add_directed_edge(19, zeroth_node);
+
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.edge_weight(zeroth_node, second_node), Some(&4));
+        assert_eq!(graph.edge_weight(second_node, second_node), Some(&1));
+        assert_eq!(graph.edge_weight(19, zeroth_node), Some(&1));
+        assert_eq!(graph[19], 'T');
+
+        // A graph made with room for its nodes holds the same edges
+        let mut graph3: MatrixGraph<u32> = MatrixGraph::with_capacity(3);
+        for i in 0..5{
+            graph3.add_node(i);
+        }
+        graph3.add_weighted_directed_edge(4, 0, 2);
+        graph3.add_weighted_directed_edge(0, 4, 7);
+
+        assert_eq!(graph3.edge_weight(4, 0), Some(&2));
+        assert_eq!(graph3.neighbors(0).collect::<Vec<u32>>(), vec![4]);
+        assert_eq!(graph3.remove_edge(4, 0), Some(2));
+        assert_eq!(graph3.edge_weight(4, 0), None);
+        assert_eq!(graph3.edge_count(), 1);
+
+        // Undirected edges are kept both ways
+        let mut graph2: MatrixGraph<u32, i64, Undirected> = MatrixGraph::new_undirected();
+
+        let node1_id = graph2.add_node(1);
+        let node2_id = graph2.add_node(2);
+
+        graph2.add_weighted_undirected_edge(node1_id, node2_id, 6);
+        graph2.add_undirected_edge(node2_id, node2_id);
+
+        assert_eq!(graph2.edge_count(), 2);
+        assert_eq!(graph2.edge_weight(node2_id, node1_id), Some(&6));
+
+        assert_eq!(graph2.remove_edge(node2_id, node1_id), Some(6));
+        assert!(!graph2.has_edge(node1_id, node2_id));
+        assert_eq!(graph2.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_backends_agree(){
+        for seed in 0..10{
+            let (matrix, list) = random_graphs(12, 30, seed);
+
+            assert_eq!(matrix.floyd_warshall(), list.floyd_warshall());
+            assert_eq!(matrix.transitive_closure(), list.transitive_closure());
+
+            let frozen = list.freeze();
+
+            assert_eq!(floyd_warshall(&frozen), matrix.floyd_warshall());
+            assert_eq!(transitive_closure(&frozen), matrix.transitive_closure());
+        }
+    }
+
+    #[test]
+    fn test_transitive_closure(){
+        let mut graph = MatrixGraph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_directed_edge(zeroth_node, first_node);
+        graph.add_directed_edge(first_node, second_node);
+        graph.add_directed_edge(second_node, first_node);
+
+        let closure = graph.transitive_closure();
+
+        assert!(closure.reaches(zeroth_node, second_node));
+        assert!(closure.reaches(first_node, first_node));
+        assert!(!closure.reaches(zeroth_node, zeroth_node));
+        assert!(!closure.reaches(first_node, zeroth_node));
+        assert!(!closure.reaches(zeroth_node, third_node));
+        assert!(!closure.reaches(zeroth_node, 7));
+        assert_eq!(closure.reachable[third_node as usize], vec![false; 4]);
+    }
+}
@@ -0,0 +1,511 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use graph::{Graph, Undirected};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A split of the nodes of a graph into two non-empty sides
+pub struct MinCut{
+    /// The total weight of the edges between the two sides
+    pub weight: i64,
+    /// The sorted IDs of the nodes on the side holding the smallest node ID
+    pub first: Vec<u32>,
+    /// The sorted IDs of the nodes on the other side
+    pub second: Vec<u32>
+}
+
+impl<T> Graph<T, i64, Undirected>{
+
+    /// Find a global minimum cut using the Stoer-Wagner algorithm, which splits the nodes into
+    /// two sides with the smallest total weight of edges between them.  Returns `None` if the
+    /// graph has fewer than two nodes.  Runs in O(n^3) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge has a negative weight.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create two triangles joined by a light edge
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let nodes: Vec<u32> = (0..6).map(|i| graph.add_node(i)).collect();
+    ///
+    /// for i in 0..3{
+    ///     graph.add_weighted_undirected_edge(nodes[i], nodes[(i + 1) % 3], 5);
+    ///     graph.add_weighted_undirected_edge(nodes[i + 3], nodes[(i + 1) % 3 + 3], 5);
+    /// }
+    /// graph.add_weighted_undirected_edge(nodes[2], nodes[3], 1);
+    ///
+    /// let cut = graph.stoer_wagner().unwrap();
+    ///
+    /// assert_eq!(cut.weight, 1);
+    /// assert_eq!(cut.first, vec![nodes[0], nodes[1], nodes[2]]);
+    /// assert_eq!(cut.second, vec![nodes[3], nodes[4], nodes[5]]);
+    /// ```
+    pub fn stoer_wagner(&self) -> Option<MinCut>{
+        let node_ids = self.sorted_node_ids();
+        if node_ids.len() < 2{
+            return None;
+        }
+
+        let mut contraction = Contraction::new(self.undirected_weights(&node_ids));
+        let mut best: Option<(u128, Vec<usize>)> = None;
+
+        while contraction.active.len() > 1{
+            // Grow a set by always adding the node most tightly connected to it
+            let mut connection: HashMap<usize, u128> = contraction.active.iter().map(|&node| (node, 0)).collect();
+            let mut order = Vec::new();
+
+            while !connection.is_empty(){
+                let &next = connection.keys().max_by_key(|&&node| (connection[&node], Reverse(node))).unwrap();
+                let weight = connection.remove(&next).unwrap();
+
+                for (&node, total) in connection.iter_mut(){
+                    *total += contraction.weights[next][node];
+                }
+                order.push((next, weight));
+            }
+
+            // Cutting off the last node added is a minimum cut between it and the one before
+            let (last, cut_weight) = order.pop().unwrap();
+            let (before, _) = order.pop().unwrap();
+
+            if best.as_ref().is_none_or(|&(best_weight, _)| cut_weight < best_weight){
+                best = Some((cut_weight, contraction.groups[last].clone()));
+            }
+
+            contraction.merge(before, last);
+        }
+
+        let (weight, side) = best.unwrap();
+        Some(MinCut::from_side(&node_ids, weight, &side))
+    }
+
+    /// Find a global minimum cut using the randomised Karger-Stein algorithm, which contracts
+    /// random edges, chosen in proportion to their weight, down to a few nodes in two
+    /// independent branches and recurses on each.  The search is repeated O(log^2 n) times so
+    /// the result is a minimum cut with high probability, but it may be larger.  The same seed
+    /// always gives the same result.  Returns `None` if the graph has fewer than two nodes.
+    /// # Arguments
+    ///
+    /// `seed` - The seed for the random choice of edges
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge has a negative weight.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create two triangles joined by a light edge
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let nodes: Vec<u32> = (0..6).map(|i| graph.add_node(i)).collect();
+    ///
+    /// for i in 0..3{
+    ///     graph.add_weighted_undirected_edge(nodes[i], nodes[(i + 1) % 3], 5);
+    ///     graph.add_weighted_undirected_edge(nodes[i + 3], nodes[(i + 1) % 3 + 3], 5);
+    /// }
+    /// graph.add_weighted_undirected_edge(nodes[2], nodes[3], 1);
+    ///
+    /// let cut = graph.karger_stein(7).unwrap();
+    ///
+    /// assert_eq!(cut.weight, 1);
+    /// assert_eq!(cut.first, vec![nodes[0], nodes[1], nodes[2]]);
+    /// ```
+    pub fn karger_stein(&self, seed: u64) -> Option<MinCut>{
+        let node_ids = self.sorted_node_ids();
+        if node_ids.len() < 2{
+            return None;
+        }
+
+        let contraction = Contraction::new(self.undirected_weights(&node_ids));
+        let mut random = Random{state: seed};
+
+        let levels = (usize::BITS - node_ids.len().leading_zeros()) as usize;
+        let mut best: Option<(u128, Vec<usize>)> = None;
+
+        for _ in 0..levels * levels{
+            let (weight, side) = contraction.clone().karger_stein(&mut random);
+
+            if best.as_ref().is_none_or(|&(best_weight, _)| weight < best_weight){
+                best = Some((weight, side));
+            }
+        }
+
+        let (weight, side) = best.unwrap();
+        Some(MinCut::from_side(&node_ids, weight, &side))
+    }
+
+    /// Build a matrix of the weight between each pair of nodes, indexed by position in
+    /// `node_ids`.  Parallel edges add up and self loops are left out.  The weights are kept as
+    /// u128 so that merged nodes and running totals can not overflow.
+    fn undirected_weights(&self, node_ids: &[u32]) -> Vec<Vec<u128>>{
+        let indices: HashMap<u32, usize> = node_ids.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+        let mut weights = vec![vec![0; node_ids.len()]; node_ids.len()];
+
+        for (i, node_id) in node_ids.iter().enumerate(){
+            for edge in self.edges(*node_id){
+                assert!(edge.weight >= 0, "minimum cuts need non-negative edge weights");
+
+                if let Some(&j) = indices.get(&edge.to){
+                    if i != j{
+                        weights[i][j] += edge.weight as u128;
+                    }
+                }
+            }
+        }
+
+        weights
+    }
+}
+
+impl MinCut{
+
+    /// Build a cut from the indices of the nodes on one side.  A weight too large for an i64
+    /// is clamped to the largest i64.
+    fn from_side(node_ids: &[u32], weight: u128, side: &[usize]) -> Self{
+        let mut on_side = vec![false; node_ids.len()];
+        for &node in side{
+            on_side[node] = true;
+        }
+
+        // Keep the smallest node ID on the first side
+        let first_side = on_side[0];
+        let mut cut = MinCut{weight: weight.min(i64::MAX as u128) as i64, first: Vec::new(), second: Vec::new()};
+        for (node, &node_id) in node_ids.iter().enumerate(){
+            if on_side[node] == first_side{
+                cut.first.push(node_id);
+            }else{
+                cut.second.push(node_id);
+            }
+        }
+
+        cut
+    }
+}
+
+#[derive(Clone)]
+/// A graph where nodes are merged together, stored as a matrix of weights between the
+/// remaining nodes
+struct Contraction{
+    weights: Vec<Vec<u128>>,
+    // The total weight of the edges of each remaining node, kept up to date as nodes merge
+    row_sums: Vec<u128>,
+    // The original nodes merged into each remaining node
+    groups: Vec<Vec<usize>>,
+    // The remaining nodes, in increasing order
+    active: Vec<usize>
+}
+
+impl Contraction{
+
+    fn new(weights: Vec<Vec<u128>>) -> Self{
+        let size = weights.len();
+        let row_sums = weights.iter().map(|row| row.iter().sum()).collect();
+        Contraction{weights, row_sums, groups: (0..size).map(|node| vec![node]).collect(), active: (0..size).collect()}
+    }
+
+    /// Merge `removed` into `kept`, adding up the weights of their edges
+    fn merge(&mut self, kept: usize, removed: usize){
+        // Edges to other nodes move over to the kept node, so only the edge between the two
+        // disappears from their sums
+        let between = self.weights[kept][removed];
+        self.row_sums[kept] = self.row_sums[kept] + self.row_sums[removed] - 2 * between;
+        self.row_sums[removed] = 0;
+
+        for &node in &self.active{
+            let weight = self.weights[removed][node];
+            self.weights[kept][node] += weight;
+            self.weights[node][kept] += weight;
+        }
+        self.weights[kept][kept] = 0;
+
+        let members = std::mem::take(&mut self.groups[removed]);
+        self.groups[kept].extend(members);
+        self.active.retain(|&node| node != removed);
+    }
+
+    /// Contract random edges until only `target` nodes remain
+    fn contract(&mut self, target: usize, random: &mut Random){
+        while self.active.len() > target{
+            // Every edge is counted once from each end
+            let total: u128 = self.active.iter().map(|&node| self.row_sums[node]).sum();
+
+            // With no edges left every cut is empty, so merging any two nodes loses nothing
+            if total == 0{
+                let (kept, removed) = (self.active[0], self.active[1]);
+                self.merge(kept, removed);
+                continue;
+            }
+
+            // Pick an end of an edge in proportion to weight, then the edge among its own
+            let remaining = random.below(total);
+            let (kept, remaining) = choose(&self.active, remaining, |node| self.row_sums[node]);
+            let (removed, _) = choose(&self.active, remaining, |node| self.weights[kept][node]);
+
+            self.merge(kept, removed);
+        }
+    }
+
+    /// Contract down to about `n / sqrt(2)` nodes twice and recurse on both, returning the
+    /// lighter cut as its weight and the original nodes on one side
+    fn karger_stein(self, random: &mut Random) -> (u128, Vec<usize>){
+        let size = self.active.len();
+
+        if size <= 6{
+            return self.brute_force();
+        }
+
+        let target = 1 + (size as f64 / 2f64.sqrt()).ceil() as usize;
+        let mut best: Option<(u128, Vec<usize>)> = None;
+
+        for _ in 0..2{
+            let mut branch = self.clone();
+            branch.contract(target, random);
+            let (weight, side) = branch.karger_stein(random);
+
+            if best.as_ref().is_none_or(|&(best_weight, _)| weight < best_weight){
+                best = Some((weight, side));
+            }
+        }
+
+        best.unwrap()
+    }
+
+    /// Try every split of the few remaining nodes
+    fn brute_force(&self) -> (u128, Vec<usize>){
+        let size = self.active.len();
+        let mut best: Option<(u128, usize)> = None;
+
+        // The first node is always on the chosen side, and the other side must not be empty
+        for others in 0..(1usize << (size - 1)) - 1{
+            let chosen = (others << 1) | 1;
+
+            let mut weight = 0;
+            for (i, &a) in self.active.iter().enumerate(){
+                for (j, &b) in self.active.iter().enumerate(){
+                    if chosen & (1 << i) != 0 && chosen & (1 << j) == 0{
+                        weight += self.weights[a][b];
+                    }
+                }
+            }
+
+            if best.is_none_or(|(best_weight, _)| weight < best_weight){
+                best = Some((weight, chosen));
+            }
+        }
+
+        let (weight, chosen) = best.unwrap();
+        let side = self.active.iter().enumerate()
+            .filter(|&(i, _)| chosen & (1 << i) != 0)
+            .flat_map(|(_, &node)| self.groups[node].iter().cloned())
+            .collect();
+
+        (weight, side)
+    }
+}
+
+/// Walk along `nodes` taking away the weight of each until `remaining` falls inside one.
+/// Returns that node and how far into its weight `remaining` fell.
+fn choose<F: Fn(usize) -> u128>(nodes: &[usize], mut remaining: u128, weight: F) -> (usize, u128){
+    for &node in nodes{
+        if remaining < weight(node){
+            return (node, remaining);
+        }
+        remaining -= weight(node);
+    }
+
+    panic!("the weights add up to less than the chosen value")
+}
+
+/// A small seedable random number generator using the SplitMix64 sequence
+struct Random{
+    state: u64
+}
+
+impl Random{
+
+    /// Returns a random number below `bound`
+    fn below(&mut self, bound: u128) -> u128{
+        // Only draw a second word when the bound needs more than one
+        let high = if bound > u64::MAX as u128 { (self.next() as u128) << 64 } else { 0 };
+        (high | self.next() as u128) % bound
+    }
+
+    /// Returns the next random word
+    fn next(&mut self) -> u64{
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+
+        let mut mixed = self.state;
+        mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94D049BB133111EB);
+        mixed ^ (mixed >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use graph::{Graph, Undirected};
+    use graph::min_cut::MinCut;
+    use graph::test_util::random_undirected_graph;
+
+    /// Build the example graph from Stoer and Wagner's paper, which has a minimum cut of 4
+    fn example_graph() -> Graph<u32, i64, Undirected>{
+        let mut graph = Graph::new_undirected();
+
+        for i in 0..8{
+            graph.add_node(i);
+        }
+
+        let edges = [(0, 1, 2), (0, 4, 3), (1, 2, 3), (1, 4, 2), (1, 5, 2), (2, 3, 4),
+                     (2, 6, 2), (3, 6, 2), (3, 7, 2), (4, 5, 3), (5, 6, 1), (6, 7, 3)];
+        for &(a, b, weight) in edges.iter(){
+            graph.add_weighted_undirected_edge(a, b, weight);
+        }
+
+        graph
+    }
+
+    fn random_graph(n: u32, m: u32, seed: u64) -> Graph<u32, i64, Undirected>{
+        random_undirected_graph(n, m, seed, 0..10)
+    }
+
+    /// Check that the sides split the nodes and the weight matches the edges between them
+    fn check_cut(graph: &Graph<u32, i64, Undirected>, cut: &MinCut){
+        let mut all: Vec<u32> = cut.first.iter().chain(cut.second.iter()).cloned().collect();
+        all.sort();
+        assert_eq!(all, graph.sorted_node_ids());
+        assert!(!cut.first.is_empty() && !cut.second.is_empty());
+        assert_eq!(cut.first[0], 0);
+
+        let crossing: i64 = cut.first.iter()
+            .flat_map(|&node_id| graph.edges(node_id))
+            .filter(|edge| cut.second.contains(&edge.to))
+            .map(|edge| edge.weight)
+            .sum();
+        assert_eq!(crossing, cut.weight);
+    }
+
+    /// Find the minimum cut weight by trying every split
+    fn brute_force(graph: &Graph<u32, i64, Undirected>) -> i64{
+        let size = graph.node_count();
+
+        (1..(1u32 << (size - 1))).map(|second| {
+            let second = second << 1;
+            graph.sorted_node_ids().into_iter()
+                .flat_map(|node_id| graph.edges(node_id))
+                .filter(|edge| second & (1 << edge.from) == 0 && second & (1 << edge.to) != 0)
+                .map(|edge| edge.weight)
+                .sum()
+        }).min().unwrap()
+    }
+
+    #[test]
+    fn test_stoer_wagner(){
+        let graph = example_graph();
+        let cut = graph.stoer_wagner().unwrap();
+
+        assert_eq!(cut.weight, 4);
+        assert_eq!(cut.first, vec![0, 1, 4, 5]);
+        assert_eq!(cut.second, vec![2, 3, 6, 7]);
+        check_cut(&graph, &cut);
+
+        for seed in 0..20{
+            let graph = random_graph(2 + seed as u32 % 9, 12, seed);
+            let cut = graph.stoer_wagner().unwrap();
+
+            check_cut(&graph, &cut);
+            assert_eq!(cut.weight, brute_force(&graph));
+        }
+    }
+
+    #[test]
+    fn test_karger_stein(){
+        let graph = example_graph();
+        let cut = graph.karger_stein(1).unwrap();
+
+        assert_eq!(cut.weight, 4);
+        check_cut(&graph, &cut);
+
+        for seed in 0..20{
+            let graph = random_graph(2 + seed as u32 % 12, 20, seed);
+            let cut = graph.karger_stein(seed).unwrap();
+
+            check_cut(&graph, &cut);
+            assert_eq!(cut.weight, graph.stoer_wagner().unwrap().weight);
+
+            // The same seed gives the same cut
+            assert_eq!(graph.karger_stein(seed), Some(cut));
+        }
+    }
+
+    #[test]
+    fn test_karger_stein_heavy_weights(){
+        let mut graph = Graph::new_undirected();
+
+        for i in 0..8{
+            graph.add_node(i);
+        }
+
+        // Two rings whose weights add up to more than an i64 can hold, joined by a light edge
+        let heavy = i64::MAX / 5;
+        for i in 0..4{
+            graph.add_weighted_undirected_edge(i, (i + 1) % 4, heavy);
+            graph.add_weighted_undirected_edge(i + 4, (i + 1) % 4 + 4, heavy);
+        }
+        graph.add_weighted_undirected_edge(3, 4, 1);
+
+        let cut = graph.karger_stein(3).unwrap();
+
+        assert_eq!(cut, MinCut{weight: 1, first: vec![0, 1, 2, 3], second: vec![4, 5, 6, 7]});
+        assert_eq!(graph.stoer_wagner(), Some(cut));
+    }
+
+    #[test]
+    fn test_stoer_wagner_heavy_weights(){
+        let mut graph = Graph::new_undirected();
+
+        // A star whose merged edges add up to more than an i64 can hold
+        let heavy = i64::MAX / 2;
+        let centre = graph.add_node(0);
+        for i in 1..4{
+            let leaf = graph.add_node(i);
+            graph.add_weighted_undirected_edge(centre, leaf, heavy);
+        }
+
+        let cut = graph.stoer_wagner().unwrap();
+
+        assert_eq!(cut.weight, heavy);
+        check_cut(&graph, &cut);
+        assert_eq!(graph.karger_stein(0).unwrap().weight, heavy);
+    }
+
+    #[test]
+    fn test_min_cut_small_graphs(){
+        let mut graph = Graph::new_undirected();
+
+        assert!(graph.stoer_wagner().is_none());
+
+        let zeroth_node = graph.add_node(0);
+
+        assert!(graph.karger_stein(0).is_none());
+
+        // Separate pieces have a cut of weight 0
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        graph.add_weighted_undirected_edge(first_node, second_node, 3);
+
+        let expected = MinCut{weight: 0, first: vec![zeroth_node], second: vec![first_node, second_node]};
+
+        assert_eq!(graph.stoer_wagner(), Some(expected.clone()));
+        assert_eq!(graph.karger_stein(0), Some(expected));
+
+        graph.add_weighted_undirected_edge(zeroth_node, first_node, 5);
+
+        assert_eq!(graph.stoer_wagner().unwrap().weight, 3);
+        assert_eq!(graph.karger_stein(0).unwrap().second, vec![second_node]);
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use graph::{Direction, Graph};
+
+impl<T, E, D: Direction> Graph<T, E, D>{
+
+    /// Rank the nodes of the graph with PageRank.  A walker follows a random edge out of its
+    /// node with probability `damping` and jumps to a random node otherwise, and the rank of a
+    /// node is the share of time the walker spends there.  Nodes without edges send the walker
+    /// to a random node.  Returns the rank of every node after `iterations` rounds of power
+    /// iteration, and the ranks always add up to 1.
+    /// # Arguments
+    ///
+    /// `damping` - The chance of following an edge rather than jumping, usually 0.85
+    /// `iterations` - The number of rounds to run
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node1, node3);
+    /// graph.add_directed_edge(node2, node3);
+    /// graph.add_directed_edge(node3, node1);
+    ///
+    /// let ranks = graph.page_rank(0.85, 50);
+    ///
+    /// assert!(ranks[&node3] > ranks[&node1]);
+    /// assert!(ranks[&node1] > ranks[&node2]);
+    /// ```
+    pub fn page_rank(&self, damping: f64, iterations: usize) -> HashMap<u32, f64>{
+        let node_ids = self.sorted_node_ids();
+        let indices: HashMap<u32, usize> = node_ids.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+
+        let adjacency: Vec<Vec<usize>> = node_ids.iter().map(|node_id| {
+            self.neighbors(*node_id).filter_map(|to| indices.get(&to).cloned()).collect()
+        }).collect();
+
+        let ranks = page_rank_by_index(node_ids.len(), damping, iterations, |node| adjacency[node].iter().cloned());
+
+        node_ids.into_iter().zip(ranks).collect()
+    }
+}
+
+/// Run PageRank on nodes given by index, where `targets` lists the index of the node each edge
+/// of a node goes to.  Returns the rank of each node by index.
+pub(crate) fn page_rank_by_index<F, I>(size: usize, damping: f64, iterations: usize, targets: F) -> Vec<f64>
+    where F: Fn(usize) -> I, I: Iterator<Item = usize> {
+    if size == 0{
+        return Vec::new();
+    }
+
+    let out_degrees: Vec<usize> = (0..size).map(|node| targets(node).count()).collect();
+    let mut ranks = vec![1.0 / size as f64; size];
+
+    for _ in 0..iterations{
+        // Rank stuck on nodes without edges is spread over every node, along with the jumps
+        let stuck: f64 = (0..size).filter(|&node| out_degrees[node] == 0).map(|node| ranks[node]).sum();
+        let mut next = vec![(1.0 - damping + damping * stuck) / size as f64; size];
+
+        for node in 0..size{
+            if out_degrees[node] > 0{
+                let share = damping * ranks[node] / out_degrees[node] as f64;
+                for target in targets(node){
+                    next[target] += share;
+                }
+            }
+        }
+
+        ranks = next;
+    }
+
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_page_rank(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        // A cycle where every node has the same rank, and one node with no edges
+        graph.add_directed_edge(zeroth_node, first_node);
+        graph.add_directed_edge(first_node, second_node);
+        graph.add_directed_edge(second_node, zeroth_node);
+
+        let ranks = graph.page_rank(0.85, 200);
+
+        assert_eq!(ranks.len(), 4);
+        assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!((ranks[&zeroth_node] - ranks[&first_node]).abs() < 1e-9);
+        assert!((ranks[&first_node] - ranks[&second_node]).abs() < 1e-9);
+        assert!(ranks[&third_node] < ranks[&zeroth_node]);
+
+        // The node with no edges only gets the jumps, including its own
+        let third = 0.15 / 4.0 + 0.85 * ranks[&third_node] / 4.0;
+        assert!((ranks[&third_node] - third).abs() < 1e-9);
+
+        // Without following edges every node is equally likely
+        let uniform = graph.page_rank(0.0, 10);
+
+        assert!(uniform.values().all(|&rank| (rank - 0.25).abs() < 1e-12));
+
+        // An undirected star ranks its centre highest
+        let mut graph2 = Graph::new_undirected();
+
+        let node1_id = graph2.add_node(1);
+        let node2_id = graph2.add_node(2);
+        let node3_id = graph2.add_node(3);
+
+        graph2.add_undirected_edge(node1_id, node2_id);
+        graph2.add_undirected_edge(node1_id, node3_id);
+
+        let ranks = graph2.page_rank(0.85, 100);
+
+        assert!(ranks[&node1_id] > ranks[&node2_id]);
+        assert!((ranks[&node2_id] - ranks[&node3_id]).abs() < 1e-9);
+
+        let empty: Graph<u32> = Graph::new();
+
+        assert!(empty.page_rank(0.85, 10).is_empty());
+    }
+}
@@ -0,0 +1,702 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::error::Error;
+use std::fmt;
+
+use graph::{Adjacency, Directed, Direction, Edge, Graph, Weight, Weighted};
+
+#[derive(Debug, Clone, PartialEq)]
+/// The result of a single source shortest path search
+pub struct ShortestPaths<E = i64>{
+    /// The ID of the node the search started from
+    pub source: u32,
+    /// The length of the shortest path from the source to every reachable node
+    pub distances: HashMap<u32, E>,
+    /// The node before each reachable node on its shortest path from the source
+    pub predecessors: HashMap<u32, u32>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Error returned when a negative weight cycle can be reached from the source of a search,
+/// meaning some shortest paths are infinitely short
+pub struct NegativeCycle{
+    /// The IDs of the nodes on the cycle, in the order the edges are followed.
+    /// The last node has an edge back to the first.
+    pub cycle: Vec<u32>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The result of an A* search between two nodes
+pub struct AStarPath<E = i64>{
+    /// The IDs of the nodes on the path, including the start and goal
+    pub path: Vec<u32>,
+    /// The total weight of the edges on the path
+    pub cost: E,
+    /// The number of nodes that were expanded during the search
+    pub expanded: usize
+}
+
+/// Distances and predecessors of the nodes reached by a search
+pub(crate) type SearchTree<W> = (HashMap<u32, W>, HashMap<u32, u32>);
+
+/// Heap entry that puts the smallest key at the top of a max heap.  Keys only need a partial
+/// order so floating point distances can be used, and keys that can not be compared are treated
+/// as equal.
+pub(crate) struct Lowest<K>(pub(crate) K);
+
+impl<K: PartialOrd> PartialEq for Lowest<K>{
+    fn eq(&self, other: &Self) -> bool{
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K: PartialOrd> Eq for Lowest<K>{}
+
+impl<K: PartialOrd> PartialOrd for Lowest<K>{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering>{
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: PartialOrd> Ord for Lowest<K>{
+    fn cmp(&self, other: &Self) -> Ordering{
+        other.0.partial_cmp(&self.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl fmt::Display for NegativeCycle{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "negative weight cycle through nodes {:?}", self.cycle)
+    }
+}
+
+impl Error for NegativeCycle{}
+
+impl<E: Copy> ShortestPaths<E>{
+
+    /// Returns the length of the shortest path from the source to `target`,
+    /// or `None` if `target` can not be reached
+    /// # Arguments
+    ///
+    /// `target` - The ID of the node to get the distance to
+    pub fn distance_to(&self, target: u32) -> Option<E>{
+        self.distances.get(&target).cloned()
+    }
+
+    /// Returns the IDs of the nodes on the shortest path from the source to `target`, including
+    /// both ends, or `None` if `target` can not be reached
+    /// # Arguments
+    ///
+    /// `target` - The ID of the node the path should end at
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_weighted_directed_edge(node1, node2, 2);
+    /// graph.add_weighted_directed_edge(node2, node3, 2);
+    /// graph.add_weighted_directed_edge(node1, node3, 5);
+    ///
+    /// let paths = graph.dijkstra(node1);
+    ///
+    /// assert_eq!(paths.path_to(node3), Some(vec![node1, node2, node3]));
+    /// ```
+    pub fn path_to(&self, target: u32) -> Option<Vec<u32>>{
+        if !self.distances.contains_key(&target){
+            return None;
+        }
+
+        // Walk the predecessors back to the source
+        let mut path = vec![target];
+        let mut current = target;
+        while current != self.source{
+            current = self.predecessors[&current];
+            path.push(current);
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<T, E: Weighted, D: Direction> Graph<T, E, D>{
+
+    /// Find the shortest paths from `source` to every reachable node using Dijkstra's algorithm.
+    /// Edges added without a weight count as weight 1.
+    /// # Arguments
+    ///
+    /// `source` - The ID of the node to find shortest paths from
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge with a negative weight is reachable from `source`.
+    /// Use `bellman_ford` for graphs with negative weights.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_weighted_directed_edge(node1, node2, 2);
+    /// graph.add_weighted_directed_edge(node2, node3, 2);
+    /// graph.add_weighted_directed_edge(node1, node3, 5);
+    ///
+    /// // Find the shortest paths from node 1
+    /// let paths = graph.dijkstra(node1);
+    ///
+    /// assert_eq!(paths.distance_to(node3), Some(4));
+    /// assert_eq!(paths.predecessors[&node3], node2);
+    /// ```
+    pub fn dijkstra(&self, source: u32) -> ShortestPaths<E::Weight>{
+        self.dijkstra_by(source, |edge| edge.weight.weight())
+    }
+
+    /// Dijkstra's algorithm using `weight` to get the length of each edge
+    pub(crate) fn dijkstra_by<W, F>(&self, source: u32, weight: F) -> ShortestPaths<W>
+        where W: Weight, F: Fn(&Edge<E>) -> W {
+        let mut distances = HashMap::new();
+        let mut predecessors = HashMap::new();
+        let mut heap = BinaryHeap::new();
+
+        if self.contains_node(source){
+            distances.insert(source, W::zero());
+            heap.push(Lowest((W::zero(), source)));
+        }
+
+        while let Some(Lowest((distance, node_id))) = heap.pop(){
+
+            // Skip entries made stale by a shorter path found later
+            if distance > distances[&node_id]{
+                continue;
+            }
+
+            for edge in self.edges(node_id){
+                if !self.contains_node(edge.to){
+                    continue;
+                }
+
+                let edge_weight = weight(edge);
+                assert!(edge_weight >= W::zero(), "dijkstra does not support negative edge weights");
+
+                let new_distance = distance + edge_weight;
+                let shorter = match distances.get(&edge.to){
+                    Some(&old_distance) => new_distance < old_distance,
+                    None => true
+                };
+
+                if shorter{
+                    distances.insert(edge.to, new_distance);
+                    predecessors.insert(edge.to, edge.from);
+                    heap.push(Lowest((new_distance, edge.to)));
+                }
+            }
+        }
+
+        ShortestPaths{source, distances, predecessors}
+    }
+
+    /// Find the shortest path from `start` to `goal` using A* search.  The heuristic estimates
+    /// the remaining cost from a node to the goal using the data stored in the node.  The path
+    /// found is a shortest path as long as the heuristic never overestimates.
+    /// Returns `None` if `goal` can not be reached.
+    /// # Arguments
+    ///
+    /// `start` - The ID of the node the path starts at
+    /// `goal` - The ID of the node the path ends at
+    /// `heuristic` - Estimates the cost of getting from a node with the given data to the goal
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge with a negative weight is reached during the search.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph of points on a line
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(0);
+    /// let node2 = graph.add_node(3);
+    /// let node3 = graph.add_node(5);
+    ///
+    /// graph.add_weighted_undirected_edge(node1, node2, 3);
+    /// graph.add_weighted_undirected_edge(node2, node3, 2);
+    /// graph.add_weighted_undirected_edge(node1, node3, 7);
+    ///
+    /// // Estimate the remaining cost with the distance along the line
+    /// let result = graph.astar(node1, node3, |x| 5 - x).unwrap();
+    ///
+    /// assert_eq!(result.path, vec![node1, node2, node3]);
+    /// assert_eq!(result.cost, 5);
+    /// ```
+    pub fn astar<F>(&self, start: u32, goal: u32, heuristic: F) -> Option<AStarPath<E::Weight>>
+        where F: Fn(&T) -> E::Weight {
+        if !self.contains_node(start) || !self.contains_node(goal){
+            return None;
+        }
+
+        let mut costs = HashMap::new();
+        let mut predecessors = HashMap::new();
+        let mut heap = BinaryHeap::new();
+        let mut expanded = 0;
+
+        costs.insert(start, E::Weight::zero());
+        heap.push(Lowest((heuristic(&self[start]), E::Weight::zero(), start)));
+
+        while let Some(Lowest((_, cost, node_id))) = heap.pop(){
+
+            // Skip entries made stale by a cheaper path found later
+            if cost > costs[&node_id]{
+                continue;
+            }
+
+            if node_id == goal{
+                let paths = ShortestPaths{source: start, distances: costs, predecessors};
+                let path = paths.path_to(goal)?;
+
+                return Some(AStarPath{path, cost, expanded});
+            }
+
+            expanded += 1;
+
+            for edge in self.edges(node_id){
+                let neighbour = match self.get_node(edge.to){
+                    Some(neighbour) => neighbour,
+                    None => continue
+                };
+
+                let edge_weight = edge.weight.weight();
+                assert!(edge_weight >= E::Weight::zero(), "astar does not support negative edge weights");
+
+                let new_cost = cost + edge_weight;
+                let cheaper = match costs.get(&edge.to){
+                    Some(&old_cost) => new_cost < old_cost,
+                    None => true
+                };
+
+                if cheaper{
+                    costs.insert(edge.to, new_cost);
+                    predecessors.insert(edge.to, edge.from);
+                    heap.push(Lowest((new_cost + heuristic(neighbour.data()), new_cost, edge.to)));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<T, E: Weighted> Graph<T, E, Directed>{
+
+    /// Find the shortest paths from `source` to every reachable node using the Bellman-Ford
+    /// algorithm.  Unlike `dijkstra`, edges may have negative weights.  Only directed graphs
+    /// are supported, as an undirected edge with a negative weight is a negative cycle on its
+    /// own.
+    /// Returns a `NegativeCycle` error holding the cycle if a negative weight cycle can be
+    /// reached from `source`.
+    /// # Arguments
+    ///
+    /// `source` - The ID of the node to find shortest paths from
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_weighted_directed_edge(node1, node2, 4);
+    /// graph.add_weighted_directed_edge(node1, node3, 2);
+    /// graph.add_weighted_directed_edge(node2, node3, -3);
+    ///
+    /// let paths = graph.bellman_ford(node1).unwrap();
+    /// assert_eq!(paths.distance_to(node3), Some(1));
+    ///
+    /// // Closing a negative cycle makes the search fail
+    /// graph.add_weighted_directed_edge(node3, node2, 1);
+    ///
+    /// let error = graph.bellman_ford(node1).unwrap_err();
+    /// assert_eq!(error.cycle, vec![node2, node3]);
+    /// ```
+    pub fn bellman_ford(&self, source: u32) -> Result<ShortestPaths<E::Weight>, NegativeCycle>{
+        if !self.contains_node(source){
+            return Ok(ShortestPaths{source, distances: HashMap::new(), predecessors: HashMap::new()});
+        }
+
+        let (distances, predecessors) = bellman_ford_from(self, &[source])?;
+
+        Ok(ShortestPaths{source, distances, predecessors})
+    }
+}
+
+/// Bellman-Ford with every node in `sources` starting at distance 0.  This is the same as
+/// searching from an extra node with a zero weight edge to each of the sources.
+/// Returns the distances and predecessors of every reachable node.
+pub(crate) fn bellman_ford_from<G>(graph: &G, sources: &[u32]) -> Result<SearchTree<<G::Payload as Weighted>::Weight>, NegativeCycle>
+    where G: Adjacency<Direction = Directed>, G::Payload: Weighted {
+    let mut distances: HashMap<u32, <G::Payload as Weighted>::Weight> = sources.iter().map(|&source| (source, Weight::zero())).collect();
+    let mut predecessors = HashMap::new();
+
+    // Visit nodes in a fixed order so the reported cycle does not depend on hashing
+    let node_ids = graph.node_ids();
+
+    // After relaxing every edge once per node, any edge that can still be relaxed is on or
+    // downstream of a negative cycle
+    for round in 0..node_ids.len(){
+        let mut relaxed = None;
+
+        for &node_id in &node_ids{
+            let distance = match distances.get(&node_id){
+                Some(&distance) => distance,
+                None => continue
+            };
+
+            graph.for_each_edge(node_id, |to, payload| {
+                let new_distance = distance + payload.weight();
+
+                if distances.get(&to).is_none_or(|&old_distance| new_distance < old_distance){
+                    distances.insert(to, new_distance);
+                    predecessors.insert(to, node_id);
+                    relaxed = Some(to);
+                }
+            });
+        }
+
+        match relaxed{
+            None => break,
+            Some(node_id) if round == node_ids.len() - 1 => {
+                return Err(NegativeCycle{cycle: trace_cycle(&predecessors, node_id, node_ids.len())});
+            },
+            Some(_) => {}
+        }
+    }
+
+    Ok((distances, predecessors))
+}
+
+/// Follow the predecessors from a node that was relaxed after the last Bellman-Ford round back
+/// into the negative cycle that caused it, and return the cycle in edge order
+pub(crate) fn trace_cycle(predecessors: &HashMap<u32, u32>, start: u32, node_count: usize) -> Vec<u32>{
+
+    // Stepping back once per node is guaranteed to land on the cycle
+    let mut on_cycle = start;
+    for _ in 0..node_count{
+        on_cycle = predecessors[&on_cycle];
+    }
+
+    let mut cycle = vec![on_cycle];
+    let mut current = predecessors[&on_cycle];
+    while current != on_cycle{
+        cycle.push(current);
+        current = predecessors[&current];
+    }
+
+    // Predecessors run against the edges, so put the smallest ID first and flip the direction
+    cycle.reverse();
+    let smallest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+    cycle.rotate_left(smallest);
+    cycle
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_dijkstra(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+
+        graph.add_weighted_directed_edge(zeroth_node, first_node, 4);
+        graph.add_weighted_directed_edge(zeroth_node, second_node, 1);
+        graph.add_weighted_directed_edge(second_node, first_node, 2);
+        graph.add_weighted_directed_edge(first_node, third_node, 1);
+        graph.add_weighted_directed_edge(second_node, third_node, 5);
+        graph.add_weighted_directed_edge(fourth_node, zeroth_node, 1);
+
+        let paths = graph.dijkstra(zeroth_node);
+
+        assert_eq!(paths.distance_to(zeroth_node), Some(0));
+        assert_eq!(paths.distance_to(first_node), Some(3));
+        assert_eq!(paths.distance_to(second_node), Some(1));
+        assert_eq!(paths.distance_to(third_node), Some(4));
+        assert_eq!(paths.distance_to(fourth_node), None);
+
+        assert_eq!(paths.predecessors[&first_node], second_node);
+        assert_eq!(paths.predecessors[&third_node], first_node);
+        assert!(!paths.predecessors.contains_key(&zeroth_node));
+
+        // Missing sources reach nothing
+        assert!(graph.dijkstra(7).distances.is_empty());
+    }
+
+    #[test]
+    fn test_dijkstra_unweighted(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_undirected_edge(zeroth_node, first_node);
+        graph.add_undirected_edge(first_node, second_node);
+
+        let paths = graph.dijkstra(second_node);
+
+        assert_eq!(paths.distance_to(zeroth_node), Some(2));
+        assert_eq!(paths.distance_to(first_node), Some(1));
+    }
+
+    #[test]
+    fn test_path_to(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_weighted_directed_edge(zeroth_node, first_node, 4);
+        graph.add_weighted_directed_edge(zeroth_node, second_node, 1);
+        graph.add_weighted_directed_edge(second_node, first_node, 2);
+
+        let paths = graph.dijkstra(zeroth_node);
+
+        assert_eq!(paths.path_to(zeroth_node), Some(vec![zeroth_node]));
+        assert_eq!(paths.path_to(first_node), Some(vec![zeroth_node, second_node, first_node]));
+        assert_eq!(paths.path_to(third_node), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dijkstra_negative_weight(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+
+        graph.add_weighted_directed_edge(zeroth_node, first_node, -1);
+
+        graph.dijkstra(zeroth_node);
+    }
+
+    #[test]
+    fn test_bellman_ford(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+
+        graph.add_weighted_directed_edge(zeroth_node, first_node, 6);
+        graph.add_weighted_directed_edge(zeroth_node, second_node, 7);
+        graph.add_weighted_directed_edge(first_node, third_node, 5);
+        graph.add_weighted_directed_edge(first_node, second_node, 8);
+        graph.add_weighted_directed_edge(second_node, third_node, -3);
+        graph.add_weighted_directed_edge(third_node, first_node, -2);
+
+        // A negative cycle that can not be reached from the source does not matter
+        graph.add_weighted_directed_edge(fourth_node, fourth_node, -1);
+
+        let paths = graph.bellman_ford(zeroth_node).unwrap();
+
+        assert_eq!(paths.distance_to(zeroth_node), Some(0));
+        assert_eq!(paths.distance_to(first_node), Some(2));
+        assert_eq!(paths.distance_to(second_node), Some(7));
+        assert_eq!(paths.distance_to(third_node), Some(4));
+        assert_eq!(paths.distance_to(fourth_node), None);
+
+        assert_eq!(paths.path_to(first_node), Some(vec![zeroth_node, second_node, third_node, first_node]));
+
+        // Non negative graphs agree with dijkstra
+        let mut graph2 = Graph::new();
+
+        let node1_id = graph2.add_node(1);
+        let node2_id = graph2.add_node(2);
+        let node3_id = graph2.add_node(3);
+
+        graph2.add_weighted_directed_edge(node1_id, node2_id, 3);
+        graph2.add_weighted_directed_edge(node2_id, node3_id, 4);
+        graph2.add_weighted_directed_edge(node3_id, node2_id, 4);
+        graph2.add_weighted_directed_edge(node1_id, node3_id, 9);
+
+        assert_eq!(graph2.bellman_ford(node1_id).unwrap().distances, graph2.dijkstra(node1_id).distances);
+    }
+
+    #[test]
+    fn test_bellman_ford_negative_cycle(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+
+        graph.add_weighted_directed_edge(zeroth_node, first_node, 1);
+        graph.add_weighted_directed_edge(first_node, second_node, 1);
+        graph.add_weighted_directed_edge(second_node, third_node, -1);
+        graph.add_weighted_directed_edge(third_node, fourth_node, 2);
+        graph.add_weighted_directed_edge(fourth_node, second_node, -2);
+
+        let error = graph.bellman_ford(zeroth_node).unwrap_err();
+
+        assert_eq!(error.cycle, vec![second_node, third_node, fourth_node]);
+
+        // Negative self loops are cycles of one node
+        let mut graph2 = Graph::new();
+
+        let node1_id = graph2.add_node(1);
+        graph2.add_weighted_directed_edge(node1_id, node1_id, -1);
+
+        assert_eq!(graph2.bellman_ford(node1_id).unwrap_err().cycle, vec![node1_id]);
+    }
+
+    #[test]
+    fn test_astar(){
+        use graph::Graph;
+
+        // Nodes are points on a grid
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node((0, 0));
+        let first_node = graph.add_node((1, 0));
+        let second_node = graph.add_node((2, 0));
+        let third_node = graph.add_node((0, 1));
+        let fourth_node = graph.add_node((0, 2));
+        let fifth_node = graph.add_node((2, 2));
+
+        graph.add_weighted_undirected_edge(zeroth_node, first_node, 1);
+        graph.add_weighted_undirected_edge(first_node, second_node, 1);
+        graph.add_weighted_undirected_edge(second_node, fifth_node, 2);
+        graph.add_weighted_undirected_edge(zeroth_node, third_node, 1);
+        graph.add_weighted_undirected_edge(third_node, fourth_node, 1);
+        graph.add_weighted_undirected_edge(fourth_node, fifth_node, 3);
+
+        let manhattan = |&(x, y): &(i64, i64)| (2 - x).abs() + (2 - y).abs();
+
+        let result = graph.astar(zeroth_node, fifth_node, manhattan).unwrap();
+
+        assert_eq!(result.path, vec![zeroth_node, first_node, second_node, fifth_node]);
+        assert_eq!(result.cost, 4);
+
+        // A better heuristic never expands more nodes than no heuristic at all
+        let blind = graph.astar(zeroth_node, fifth_node, |_| 0).unwrap();
+
+        assert_eq!(blind.path, result.path);
+        assert_eq!(blind.cost, result.cost);
+        assert!(result.expanded <= blind.expanded);
+
+        // The start is its own path
+        let result = graph.astar(zeroth_node, zeroth_node, manhattan).unwrap();
+
+        assert_eq!(result.path, vec![zeroth_node]);
+        assert_eq!(result.cost, 0);
+        assert_eq!(result.expanded, 0);
+    }
+
+    #[test]
+    fn test_astar_unreachable(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+
+        graph.add_directed_edge(first_node, zeroth_node);
+
+        assert!(graph.astar(zeroth_node, first_node, |_| 0).is_none());
+        assert!(graph.astar(zeroth_node, 7, |_| 0).is_none());
+    }
+
+    #[test]
+    fn test_float_weights(){
+        use graph::Graph;
+
+        let mut graph: Graph<u32, f64> = Graph::new_weighted();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_weighted_directed_edge(zeroth_node, first_node, 1.5);
+        graph.add_weighted_directed_edge(zeroth_node, second_node, 0.25);
+        graph.add_weighted_directed_edge(second_node, first_node, 0.5);
+        graph.add_weighted_directed_edge(first_node, third_node, 0.125);
+
+        let paths = graph.dijkstra(zeroth_node);
+
+        assert_eq!(paths.distance_to(first_node), Some(0.75));
+        assert_eq!(paths.distance_to(third_node), Some(0.875));
+        assert_eq!(paths.path_to(third_node), Some(vec![zeroth_node, second_node, first_node, third_node]));
+
+        let result = graph.astar(zeroth_node, third_node, |_| 0.0).unwrap();
+
+        assert_eq!(result.path, vec![zeroth_node, second_node, first_node, third_node]);
+        assert_eq!(result.cost, 0.875);
+
+        // Non negative graphs agree with dijkstra
+        assert_eq!(graph.bellman_ford(zeroth_node).unwrap(), paths);
+
+        // Going round the cycle saves an eighth each time
+        graph.add_weighted_directed_edge(third_node, zeroth_node, -1.0);
+
+        let error = graph.bellman_ford(zeroth_node).unwrap_err();
+
+        assert_eq!(error.cycle, vec![zeroth_node, second_node, first_node, third_node]);
+    }
+
+    #[test]
+    fn test_unsigned_weights(){
+        use graph::Graph;
+
+        let mut graph: Graph<u32, u32> = Graph::new_weighted();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_directed_edge(zeroth_node, first_node);
+        graph.add_weighted_directed_edge(first_node, second_node, 3);
+        graph.add_weighted_directed_edge(zeroth_node, second_node, 5);
+
+        let paths = graph.dijkstra(zeroth_node);
+
+        assert_eq!(paths.distance_to(second_node), Some(4u32));
+        assert_eq!(graph.bellman_ford(zeroth_node).unwrap(), paths);
+    }
+}
@@ -0,0 +1,335 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use graph::{Graph, Undirected, Weight, Weighted};
+use graph::shortest_path::Lowest;
+use tree::Tree;
+use union_find::UnionFind;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A minimum spanning forest of a graph, with one tree for each connected component
+pub struct SpanningTree<E = i64>{
+    /// The edges in the forest as `(from, to, weight)`
+    pub edges: Vec<(u32, u32, E)>,
+    /// The sum of the weights of the edges in the forest
+    pub total_weight: E
+}
+
+impl<E> SpanningTree<E>{
+
+    /// Builds the tree of the forest containing `root` as a `Tree` of node IDs, with `root`
+    /// at the top.  Nodes that are not connected to `root` are left out.
+    /// # Arguments
+    ///
+    /// `root` - The ID of the node to put at the root of the tree
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_weighted_undirected_edge(node1, node2, 1);
+    /// graph.add_weighted_undirected_edge(node1, node3, 2);
+    /// graph.add_weighted_undirected_edge(node2, node3, 3);
+    ///
+    /// let tree = graph.kruskal().to_tree(node2);
+    ///
+    /// assert_eq!(tree.preorder_traversal(), vec![node2, node1, node3]);
+    /// ```
+    pub fn to_tree(&self, root: u32) -> Tree<u32>{
+        let mut neighbours: HashMap<u32, Vec<u32>> = HashMap::new();
+
+        for &(from, to, _) in &self.edges{
+            neighbours.entry(from).or_default().push(to);
+            neighbours.entry(to).or_default().push(from);
+        }
+
+        // Walk down from the root, adding each subtree to its parent once all of its children
+        // are in it
+        let mut stack = vec![(root, None, 0, Tree::new(root))];
+        loop{
+            let &mut (node, parent, ref mut next, _) = stack.last_mut().unwrap();
+            let children = neighbours.get(&node).map_or(&[][..], |children| &children[..]);
+
+            if *next < children.len(){
+                let child = children[*next];
+                *next += 1;
+
+                if Some(child) != parent{
+                    stack.push((child, Some(node), 0, Tree::new(child)));
+                }
+            }else{
+                let (_, _, _, tree) = stack.pop().unwrap();
+
+                match stack.last_mut(){
+                    Some(top) => top.3.add_subtree(tree),
+                    None => return tree
+                }
+            }
+        }
+    }
+}
+
+impl<T, E: Weighted> Graph<T, E, Undirected>{
+
+    /// Find a minimum spanning forest using Kruskal's algorithm
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_weighted_undirected_edge(node1, node2, 1);
+    /// graph.add_weighted_undirected_edge(node1, node3, 2);
+    /// graph.add_weighted_undirected_edge(node2, node3, 3);
+    ///
+    /// let tree = graph.kruskal();
+    ///
+    /// assert_eq!(tree.edges, vec![(node1, node2, 1), (node1, node3, 2)]);
+    /// assert_eq!(tree.total_weight, 3);
+    /// ```
+    pub fn kruskal(&self) -> SpanningTree<E::Weight>{
+        let node_ids = self.sorted_node_ids();
+        let indices: HashMap<u32, usize> = node_ids.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+
+        // Both nodes of an edge keep it, so only take it from the node with the smaller ID
+        let mut edges = Vec::new();
+        for node_id in &node_ids{
+            for edge in self.edges(*node_id){
+                if indices.contains_key(&edge.to) && edge.from <= edge.to{
+                    edges.push((edge.weight.weight(), edge.from, edge.to));
+                }
+            }
+        }
+        edges.sort_by(|first, second| first.partial_cmp(second).unwrap_or(Ordering::Equal));
+
+        // Take the lightest edges that join two different trees
+        let mut sets = UnionFind::new(node_ids.len());
+        let mut tree = SpanningTree{edges: Vec::new(), total_weight: E::Weight::zero()};
+
+        for (weight, from, to) in edges{
+            if sets.union(indices[&from], indices[&to]){
+                tree.edges.push((from, to, weight));
+                tree.total_weight = tree.total_weight + weight;
+            }
+        }
+
+        tree
+    }
+
+    /// Find a minimum spanning forest using Prim's algorithm.  A tree is grown from the node
+    /// with the smallest ID in each connected component.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_weighted_undirected_edge(node1, node2, 1);
+    /// graph.add_weighted_undirected_edge(node1, node3, 2);
+    /// graph.add_weighted_undirected_edge(node2, node3, 3);
+    ///
+    /// let tree = graph.prim();
+    ///
+    /// assert_eq!(tree.edges, vec![(node1, node2, 1), (node1, node3, 2)]);
+    /// assert_eq!(tree.total_weight, 3);
+    /// ```
+    pub fn prim(&self) -> SpanningTree<E::Weight>{
+        let mut in_tree = HashSet::new();
+        let mut tree = SpanningTree{edges: Vec::new(), total_weight: E::Weight::zero()};
+
+        for root in self.sorted_node_ids(){
+            if !in_tree.insert(root){
+                continue;
+            }
+
+            let mut heap = BinaryHeap::new();
+            self.push_crossing_edges(root, &in_tree, &mut heap);
+
+            // Repeatedly take the lightest edge leaving the tree
+            while let Some(Lowest((weight, from, to))) = heap.pop(){
+                if !in_tree.insert(to){
+                    continue;
+                }
+
+                tree.edges.push((from, to, weight));
+                tree.total_weight = tree.total_weight + weight;
+
+                self.push_crossing_edges(to, &in_tree, &mut heap);
+            }
+        }
+
+        tree
+    }
+
+    /// Push the edges from `node_id` to nodes outside of the tree onto the heap
+    fn push_crossing_edges(&self, node_id: u32, in_tree: &HashSet<u32>, heap: &mut BinaryHeap<Lowest<(E::Weight, u32, u32)>>){
+        for edge in self.edges(node_id){
+            if self.contains_node(edge.to) && !in_tree.contains(&edge.to){
+                heap.push(Lowest((edge.weight.weight(), edge.from, edge.to)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_kruskal(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+
+        graph.add_weighted_undirected_edge(zeroth_node, first_node, 7);
+        graph.add_weighted_undirected_edge(zeroth_node, third_node, 5);
+        graph.add_weighted_undirected_edge(first_node, second_node, 8);
+        graph.add_weighted_undirected_edge(first_node, third_node, 9);
+        graph.add_weighted_undirected_edge(first_node, fourth_node, 7);
+        graph.add_weighted_undirected_edge(second_node, fourth_node, 5);
+        graph.add_weighted_undirected_edge(third_node, fourth_node, 15);
+
+        let tree = graph.kruskal();
+
+        let expected = vec![
+            (zeroth_node, third_node, 5),
+            (second_node, fourth_node, 5),
+            (zeroth_node, first_node, 7),
+            (first_node, fourth_node, 7),
+        ];
+
+        assert_eq!(tree.edges, expected);
+        assert_eq!(tree.total_weight, 24);
+    }
+
+    #[test]
+    fn test_prim(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+
+        graph.add_weighted_undirected_edge(zeroth_node, first_node, 7);
+        graph.add_weighted_undirected_edge(zeroth_node, third_node, 5);
+        graph.add_weighted_undirected_edge(first_node, second_node, 8);
+        graph.add_weighted_undirected_edge(first_node, third_node, 9);
+        graph.add_weighted_undirected_edge(first_node, fourth_node, 7);
+        graph.add_weighted_undirected_edge(second_node, fourth_node, 5);
+        graph.add_weighted_undirected_edge(third_node, fourth_node, 15);
+
+        let tree = graph.prim();
+
+        let expected = vec![
+            (zeroth_node, third_node, 5),
+            (zeroth_node, first_node, 7),
+            (first_node, fourth_node, 7),
+            (fourth_node, second_node, 5),
+        ];
+
+        assert_eq!(tree.edges, expected);
+        assert_eq!(tree.total_weight, graph.kruskal().total_weight);
+    }
+
+    #[test]
+    fn test_spanning_forest(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+
+        graph.add_weighted_undirected_edge(zeroth_node, first_node, 2);
+        graph.add_weighted_undirected_edge(second_node, third_node, -1);
+        graph.add_weighted_undirected_edge(third_node, fourth_node, 4);
+        graph.add_weighted_undirected_edge(second_node, fourth_node, 3);
+
+        let kruskal = graph.kruskal();
+        let prim = graph.prim();
+
+        assert_eq!(kruskal.edges.len(), 3);
+        assert_eq!(kruskal.total_weight, 4);
+        assert_eq!(prim.edges.len(), 3);
+        assert_eq!(prim.total_weight, 4);
+    }
+
+    #[test]
+    fn test_to_tree(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+
+        graph.add_weighted_undirected_edge(zeroth_node, first_node, 1);
+        graph.add_weighted_undirected_edge(first_node, second_node, 1);
+        graph.add_weighted_undirected_edge(first_node, third_node, 1);
+        graph.add_weighted_undirected_edge(zeroth_node, second_node, 5);
+
+        let tree = graph.kruskal().to_tree(first_node);
+
+        assert_eq!(tree.preorder_traversal(), vec![first_node, zeroth_node, second_node, third_node]);
+        assert_eq!(tree.postorder_traversal(), vec![zeroth_node, second_node, third_node, first_node]);
+
+        // Nodes outside the forest are trees on their own
+        let tree = graph.kruskal().to_tree(fourth_node);
+
+        assert_eq!(tree.preorder_traversal(), vec![fourth_node]);
+    }
+
+    #[test]
+    fn test_float_weights(){
+        use graph::Graph;
+
+        let mut graph: Graph<u32, f64, _> = Graph::new_weighted();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_weighted_undirected_edge(zeroth_node, first_node, 0.5);
+        graph.add_weighted_undirected_edge(first_node, second_node, 1.25);
+        graph.add_weighted_undirected_edge(zeroth_node, second_node, 2.0);
+
+        let kruskal = graph.kruskal();
+        let prim = graph.prim();
+
+        assert_eq!(kruskal.edges, vec![(zeroth_node, first_node, 0.5), (first_node, second_node, 1.25)]);
+        assert_eq!(kruskal.total_weight, 1.75);
+        assert_eq!(prim.edges, kruskal.edges);
+        assert_eq!(prim.total_weight, 1.75);
+    }
+}
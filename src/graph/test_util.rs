@@ -0,0 +1,70 @@
+use std::ops::Range;
+
+use graph::{Graph, Undirected};
+
+/// A small seedable linear congruential generator, so random tests give the same graphs on
+/// every run
+pub struct Lcg{
+    state: u64
+}
+
+impl Lcg{
+
+    /// Returns a new generator starting from `seed`
+    pub fn new(seed: u64) -> Self{
+        Lcg{state: seed}
+    }
+
+    /// Returns a random number below `bound`
+    pub fn below(&mut self, bound: u64) -> u64{
+        self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.state >> 33) % bound
+    }
+
+    /// Returns a random number in `range`
+    pub fn in_range(&mut self, range: Range<i64>) -> i64{
+        range.start + self.below((range.end - range.start) as u64) as i64
+    }
+}
+
+/// Returns `m` random edges between `n` nodes as `(from, to, weight)`, with weights drawn
+/// from `weights`.  Self loops and parallel edges are allowed.
+pub fn random_edges(n: u32, m: u32, seed: u64, weights: Range<i64>) -> Vec<(u32, u32, i64)>{
+    let mut random = Lcg::new(seed);
+
+    (0..m).map(|_| {
+        let from = random.below(u64::from(n)) as u32;
+        let to = random.below(u64::from(n)) as u32;
+        (from, to, random.in_range(weights.clone()))
+    }).collect()
+}
+
+/// Build a directed graph of `n` nodes, each holding its ID, joined by the edges of
+/// `random_edges`
+pub fn random_graph(n: u32, m: u32, seed: u64, weights: Range<i64>) -> Graph<u32>{
+    let mut graph = Graph::new();
+    for i in 0..n{
+        graph.add_node(i);
+    }
+
+    for (from, to, weight) in random_edges(n, m, seed, weights){
+        graph.add_weighted_directed_edge(from, to, weight);
+    }
+
+    graph
+}
+
+/// Build an undirected graph of `n` nodes, each holding its ID, joined by the edges of
+/// `random_edges`
+pub fn random_undirected_graph(n: u32, m: u32, seed: u64, weights: Range<i64>) -> Graph<u32, i64, Undirected>{
+    let mut graph = Graph::new_undirected();
+    for i in 0..n{
+        graph.add_node(i);
+    }
+
+    for (from, to, weight) in random_edges(n, m, seed, weights){
+        graph.add_weighted_undirected_edge(from, to, weight);
+    }
+
+    graph
+}
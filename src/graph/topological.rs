@@ -0,0 +1,321 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+
+use graph::{Directed, Graph};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Error returned when a graph that should be acyclic contains a cycle
+pub struct Cycle{
+    /// The IDs of the nodes on the cycle, in the order the edges are followed.
+    /// The last node has an edge back to the first.
+    pub cycle: Vec<u32>
+}
+
+impl fmt::Display for Cycle{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result{
+        write!(f, "graph contains a cycle through nodes {:?}", self.cycle)
+    }
+}
+
+impl Error for Cycle{}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Colour{
+    // Not visited yet
+    White,
+    // On the current depth first search path
+    Grey,
+    // Finished, along with everything reachable from it
+    Black
+}
+
+impl<T, E> Graph<T, E, Directed>{
+
+    /// Order the nodes so that every edge goes from an earlier node to a later one, using
+    /// Kahn's algorithm.  Returns a `Cycle` error holding one cycle if there is no such order.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph of build steps
+    /// let mut graph = Graph::new();
+    ///
+    /// let compile = graph.add_node("compile");
+    /// let link = graph.add_node("link");
+    /// let fetch = graph.add_node("fetch");
+    ///
+    /// graph.add_directed_edge(fetch, compile);
+    /// graph.add_directed_edge(compile, link);
+    ///
+    /// assert_eq!(graph.topological_sort(), Ok(vec![fetch, compile, link]));
+    ///
+    /// // Cycles have no order
+    /// graph.add_directed_edge(link, fetch);
+    ///
+    /// assert_eq!(graph.topological_sort().unwrap_err().cycle, vec![compile, link, fetch]);
+    /// ```
+    pub fn topological_sort(&self) -> Result<Vec<u32>, Cycle>{
+        self.kahn(false)
+    }
+
+    /// Order the nodes so that every edge goes from an earlier node to a later one, choosing
+    /// the lexicographically smallest such order of node IDs.  The order only depends on the
+    /// edges, not on the order they were added in.  Returns a `Cycle` error holding one cycle
+    /// if there is no such order.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node3, node2);
+    ///
+    /// assert_eq!(graph.lexicographic_topological_sort(), Ok(vec![node1, node3, node2]));
+    /// ```
+    pub fn lexicographic_topological_sort(&self) -> Result<Vec<u32>, Cycle>{
+        self.kahn(true)
+    }
+
+    /// Order the nodes so that every edge goes from an earlier node to a later one, using the
+    /// reverse of a depth first post-order.  Returns a `Cycle` error holding one cycle if there
+    /// is no such order.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node1, node3);
+    /// graph.add_directed_edge(node3, node2);
+    ///
+    /// assert_eq!(graph.topological_sort_dfs(), Ok(vec![node1, node3, node2]));
+    /// ```
+    pub fn topological_sort_dfs(&self) -> Result<Vec<u32>, Cycle>{
+        let mut colours: HashMap<u32, Colour> = self.sorted_node_ids().into_iter().map(|node_id| (node_id, Colour::White)).collect();
+        let mut order = Vec::new();
+
+        for root in self.sorted_node_ids(){
+            if colours[&root] != Colour::White{
+                continue;
+            }
+
+            // Each entry is a node on the current path and the index of its next edge to follow
+            let mut stack = vec![(root, 0)];
+            colours.insert(root, Colour::Grey);
+
+            while let Some(&mut (node_id, ref mut next_edge)) = stack.last_mut(){
+                let edges = self.edges(node_id).as_slice();
+
+                if *next_edge == edges.len(){
+                    stack.pop();
+                    colours.insert(node_id, Colour::Black);
+                    order.push(node_id);
+                    continue;
+                }
+
+                let to = edges[*next_edge].to;
+                *next_edge += 1;
+
+                match colours.get(&to){
+                    Some(&Colour::White) => {
+                        colours.insert(to, Colour::Grey);
+                        stack.push((to, 0));
+                    },
+                    Some(&Colour::Grey) => {
+                        // An edge back onto the current path closes a cycle
+                        let start = stack.iter().position(|&(on_path, _)| on_path == to).unwrap();
+                        let cycle = stack[start..].iter().map(|&(on_path, _)| on_path).collect();
+
+                        return Err(Cycle{cycle});
+                    },
+                    _ => {}
+                }
+            }
+        }
+
+        order.reverse();
+        Ok(order)
+    }
+
+    /// Kahn's algorithm, always taking the smallest available node ID when `smallest_first` is
+    /// set and otherwise taking nodes in the order they become available
+    fn kahn(&self, smallest_first: bool) -> Result<Vec<u32>, Cycle>{
+        let node_ids = self.sorted_node_ids();
+
+        let mut in_degrees: HashMap<u32, usize> = node_ids.iter().map(|&node_id| (node_id, 0)).collect();
+        for node_id in self.sorted_node_ids(){
+            for edge in self.edges(node_id){
+                if let Some(in_degree) = in_degrees.get_mut(&edge.to){
+                    *in_degree += 1;
+                }
+            }
+        }
+
+        let mut queue = VecDeque::new();
+        let mut heap = BinaryHeap::new();
+
+        for &node_id in &node_ids{
+            if in_degrees[&node_id] == 0{
+                if smallest_first{
+                    heap.push(Reverse(node_id));
+                }else{
+                    queue.push_back(node_id);
+                }
+            }
+        }
+
+        let mut order = Vec::new();
+
+        loop{
+            let node_id = if smallest_first{
+                match heap.pop(){
+                    Some(Reverse(node_id)) => node_id,
+                    None => break
+                }
+            }else{
+                match queue.pop_front(){
+                    Some(node_id) => node_id,
+                    None => break
+                }
+            };
+
+            order.push(node_id);
+
+            // Removing the node frees up any node that only it pointed to
+            for edge in self.edges(node_id){
+                if let Some(in_degree) = in_degrees.get_mut(&edge.to){
+                    *in_degree -= 1;
+
+                    if *in_degree == 0{
+                        if smallest_first{
+                            heap.push(Reverse(edge.to));
+                        }else{
+                            queue.push_back(edge.to);
+                        }
+                    }
+                }
+            }
+        }
+
+        if order.len() < node_ids.len(){
+            // Some nodes are stuck behind a cycle, which a depth first search will find
+            return self.topological_sort_dfs();
+        }
+
+        Ok(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_topological_sort(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+        let fifth_node = graph.add_node(5);
+
+        graph.add_directed_edge(fifth_node, second_node);
+        graph.add_directed_edge(fifth_node, zeroth_node);
+        graph.add_directed_edge(fourth_node, zeroth_node);
+        graph.add_directed_edge(fourth_node, first_node);
+        graph.add_directed_edge(second_node, third_node);
+        graph.add_directed_edge(third_node, first_node);
+
+        let order = graph.topological_sort().unwrap();
+
+        assert_eq!(order, vec![fourth_node, fifth_node, second_node, zeroth_node, third_node, first_node]);
+
+        let order = graph.topological_sort_dfs().unwrap();
+
+        assert_eq!(order, vec![fifth_node, fourth_node, second_node, third_node, first_node, zeroth_node]);
+
+        let order = graph.lexicographic_topological_sort().unwrap();
+
+        assert_eq!(order, vec![fourth_node, fifth_node, zeroth_node, second_node, third_node, first_node]);
+    }
+
+    #[test]
+    fn test_lexicographic_topological_sort(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_directed_edge(third_node, zeroth_node);
+        graph.add_directed_edge(first_node, second_node);
+        graph.add_directed_edge(first_node, zeroth_node);
+
+        // Kahn's algorithm takes nodes as they become free, the lexicographic order does not
+        assert_eq!(graph.topological_sort(), Ok(vec![first_node, third_node, second_node, zeroth_node]));
+        assert_eq!(graph.lexicographic_topological_sort(), Ok(vec![first_node, second_node, third_node, zeroth_node]));
+
+        // Edge insertion order does not change the lexicographic order
+        let mut graph2 = Graph::new();
+
+        let zeroth_node = graph2.add_node(0);
+        let first_node = graph2.add_node(1);
+        let second_node = graph2.add_node(2);
+        let third_node = graph2.add_node(3);
+
+        graph2.add_directed_edge(first_node, zeroth_node);
+        graph2.add_directed_edge(first_node, second_node);
+        graph2.add_directed_edge(third_node, zeroth_node);
+
+        assert_eq!(graph2.lexicographic_topological_sort(), Ok(vec![first_node, second_node, third_node, zeroth_node]));
+    }
+
+    #[test]
+    fn test_topological_sort_cycle(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_directed_edge(zeroth_node, first_node);
+        graph.add_directed_edge(first_node, second_node);
+        graph.add_directed_edge(second_node, third_node);
+        graph.add_directed_edge(third_node, first_node);
+
+        let expected = vec![first_node, second_node, third_node];
+
+        assert_eq!(graph.topological_sort().unwrap_err().cycle, expected);
+        assert_eq!(graph.topological_sort_dfs().unwrap_err().cycle, expected);
+        assert_eq!(graph.lexicographic_topological_sort().unwrap_err().cycle, expected);
+
+        // Self loops are cycles too
+        let mut graph2 = Graph::new();
+
+        let node1_id = graph2.add_node(1);
+        graph2.add_directed_edge(node1_id, node1_id);
+
+        assert_eq!(graph2.topological_sort().unwrap_err().cycle, vec![node1_id]);
+    }
+}
@@ -0,0 +1,328 @@
+use std::collections::{HashSet, VecDeque};
+
+use graph::{Directed, Direction, Graph};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A node reached during a traversal of a graph
+pub struct Visit{
+    /// The ID of the node that was reached
+    pub node: u32,
+    /// The number of edges between the start node and this node in the traversal
+    pub depth: u32,
+    /// The ID of the node this node was discovered from, `None` for the start node
+    pub parent: Option<u32>
+}
+
+/// Lazy breadth first traversal of the nodes reachable from a start node
+pub struct Bfs<'a, T: 'a, E: 'a = i64, D: 'a = Directed>{
+    graph: &'a Graph<T, E, D>,
+    queue: VecDeque<Visit>,
+    discovered: HashSet<u32>
+}
+
+/// Lazy depth first traversal of the nodes reachable from a start node
+pub struct Dfs<'a, T: 'a, E: 'a = i64, D: 'a = Directed>{
+    graph: &'a Graph<T, E, D>,
+    // Each entry is a node on the current path and the index of the next edge to explore from it
+    stack: Vec<(Visit, usize)>,
+    discovered: HashSet<u32>,
+    // The start node, until it has been yielded by a pre-order traversal
+    pending: Option<Visit>,
+    postorder: bool
+}
+
+impl<T, E, D: Direction> Graph<T, E, D>{
+
+    /// Returns an iterator over the nodes reachable from `start` in breadth first order.
+    /// Nodes are discovered lazily, so the traversal can be stopped early.
+    /// # Arguments
+    ///
+    /// `start` - The ID of the node to start the traversal at
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node1, node2);
+    /// graph.add_directed_edge(node2, node3);
+    ///
+    /// // Visit the nodes in breadth first order, along with their depth
+    /// let order: Vec<(u32, u32)> = graph.bfs(node1).map(|v| (v.node, v.depth)).collect();
+    ///
+    /// assert_eq!(order, vec![(node1, 0), (node2, 1), (node3, 2)]);
+    /// ```
+    pub fn bfs(&self, start: u32) -> Bfs<'_, T, E, D>{
+        let mut queue = VecDeque::new();
+        let mut discovered = HashSet::new();
+
+        if self.contains_node(start){
+            queue.push_back(Visit{node: start, depth: 0, parent: None});
+            discovered.insert(start);
+        }
+
+        Bfs{graph: self, queue, discovered}
+    }
+
+    /// Returns an iterator over the nodes reachable from `start` in depth first pre-order,
+    /// meaning each node is yielded as soon as it is discovered.
+    /// # Arguments
+    ///
+    /// `start` - The ID of the node to start the traversal at
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node1, node2);
+    /// graph.add_directed_edge(node1, node3);
+    ///
+    /// // Visit the nodes in depth first pre-order
+    /// let order: Vec<u32> = graph.dfs(node1).map(|v| v.node).collect();
+    ///
+    /// assert_eq!(order, vec![node1, node2, node3]);
+    /// ```
+    pub fn dfs(&self, start: u32) -> Dfs<'_, T, E, D>{
+        Dfs::new(self, start, false)
+    }
+
+    /// Returns an iterator over the nodes reachable from `start` in depth first post-order,
+    /// meaning each node is yielded once all of its descendants have been yielded.
+    /// # Arguments
+    ///
+    /// `start` - The ID of the node to start the traversal at
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_directed_edge(node1, node2);
+    /// graph.add_directed_edge(node1, node3);
+    ///
+    /// // Visit the nodes in depth first post-order
+    /// let order: Vec<u32> = graph.dfs_postorder(node1).map(|v| v.node).collect();
+    ///
+    /// assert_eq!(order, vec![node2, node3, node1]);
+    /// ```
+    pub fn dfs_postorder(&self, start: u32) -> Dfs<'_, T, E, D>{
+        Dfs::new(self, start, true)
+    }
+}
+
+impl<'a, T, E, D: Direction> Iterator for Bfs<'a, T, E, D>{
+    type Item = Visit;
+
+    fn next(&mut self) -> Option<Visit>{
+        let visit = self.queue.pop_front()?;
+
+        // Queue up every neighbour that has not been seen yet
+        for edge in self.graph.edges(visit.node){
+            if self.graph.contains_node(edge.to) && self.discovered.insert(edge.to){
+                self.queue.push_back(Visit{node: edge.to, depth: visit.depth + 1, parent: Some(edge.from)});
+            }
+        }
+
+        Some(visit)
+    }
+}
+
+impl<'a, T, E, D: Direction> Dfs<'a, T, E, D>{
+
+    fn new(graph: &'a Graph<T, E, D>, start: u32, postorder: bool) -> Self{
+        let mut dfs = Dfs{graph, stack: Vec::new(), discovered: HashSet::new(), pending: None, postorder};
+
+        if graph.contains_node(start){
+            let visit = Visit{node: start, depth: 0, parent: None};
+
+            dfs.stack.push((visit, 0));
+            dfs.discovered.insert(start);
+
+            if !postorder{
+                dfs.pending = Some(visit);
+            }
+        }
+
+        dfs
+    }
+}
+
+impl<'a, T, E, D: Direction> Iterator for Dfs<'a, T, E, D>{
+    type Item = Visit;
+
+    fn next(&mut self) -> Option<Visit>{
+        if let Some(visit) = self.pending.take(){
+            return Some(visit);
+        }
+
+        loop{
+            let child = {
+                let (visit, next_edge) = self.stack.last_mut()?;
+                let edges = self.graph.edges(visit.node).as_slice();
+
+                // Find the next neighbour of the top node that has not been discovered yet
+                let mut child = None;
+                while *next_edge < edges.len(){
+                    let edge = &edges[*next_edge];
+                    *next_edge += 1;
+
+                    if self.graph.contains_node(edge.to) && self.discovered.insert(edge.to){
+                        child = Some(Visit{node: edge.to, depth: visit.depth + 1, parent: Some(edge.from)});
+                        break;
+                    }
+                }
+                child
+            };
+
+            match child{
+                Some(visit) => {
+                    self.stack.push((visit, 0));
+
+                    if !self.postorder{
+                        return Some(visit);
+                    }
+                },
+                None => {
+                    // Every descendant of the top node is finished
+                    let (visit, _) = self.stack.pop()?;
+
+                    if self.postorder{
+                        return Some(visit);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_bfs(){
+        use graph::Graph;
+        use graph::traversal::Visit;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+        let fourth_node = graph.add_node(4);
+
+        graph.add_directed_edge(zeroth_node, first_node);
+        graph.add_directed_edge(zeroth_node, second_node);
+        graph.add_directed_edge(first_node, third_node);
+        graph.add_directed_edge(second_node, third_node);
+        graph.add_directed_edge(third_node, zeroth_node);
+
+        let result: Vec<Visit> = graph.bfs(zeroth_node).collect();
+
+        let expected = vec![
+            Visit{node: zeroth_node, depth: 0, parent: None},
+            Visit{node: first_node, depth: 1, parent: Some(zeroth_node)},
+            Visit{node: second_node, depth: 1, parent: Some(zeroth_node)},
+            Visit{node: third_node, depth: 2, parent: Some(first_node)},
+        ];
+
+        assert_eq!(expected, result);
+
+        // The fourth node is unreachable and on its own
+        let result: Vec<u32> = graph.bfs(fourth_node).map(|v| v.node).collect();
+        assert_eq!(result, vec![fourth_node]);
+
+        // Missing start nodes visit nothing
+        assert_eq!(graph.bfs(7).count(), 0);
+    }
+
+    #[test]
+    fn test_bfs_stops_early(){
+        use graph::Graph;
+
+        let mut graph = Graph::new_undirected();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        graph.add_undirected_edge(zeroth_node, first_node);
+        graph.add_undirected_edge(first_node, second_node);
+
+        let found = graph.bfs(zeroth_node).find(|v| v.node == first_node).unwrap();
+
+        assert_eq!(found.depth, 1);
+        assert_eq!(found.parent, Some(zeroth_node));
+    }
+
+    #[test]
+    fn test_dfs(){
+        use graph::Graph;
+        use graph::traversal::Visit;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_directed_edge(zeroth_node, first_node);
+        graph.add_directed_edge(zeroth_node, second_node);
+        graph.add_directed_edge(first_node, third_node);
+        graph.add_directed_edge(third_node, second_node);
+        graph.add_directed_edge(third_node, zeroth_node);
+
+        let result: Vec<Visit> = graph.dfs(zeroth_node).collect();
+
+        let expected = vec![
+            Visit{node: zeroth_node, depth: 0, parent: None},
+            Visit{node: first_node, depth: 1, parent: Some(zeroth_node)},
+            Visit{node: third_node, depth: 2, parent: Some(first_node)},
+            Visit{node: second_node, depth: 3, parent: Some(third_node)},
+        ];
+
+        assert_eq!(expected, result);
+
+        assert_eq!(graph.dfs(7).count(), 0);
+    }
+
+    #[test]
+    fn test_dfs_postorder(){
+        use graph::Graph;
+
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+        let third_node = graph.add_node(3);
+
+        graph.add_directed_edge(zeroth_node, first_node);
+        graph.add_directed_edge(zeroth_node, second_node);
+        graph.add_directed_edge(first_node, third_node);
+        graph.add_directed_edge(third_node, zeroth_node);
+
+        let result: Vec<u32> = graph.dfs_postorder(zeroth_node).map(|v| v.node).collect();
+
+        assert_eq!(result, vec![third_node, first_node, second_node, zeroth_node]);
+
+        assert_eq!(graph.dfs_postorder(7).count(), 0);
+    }
+}
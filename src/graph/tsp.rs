@@ -0,0 +1,601 @@
+use std::collections::HashMap;
+
+use graph::{Direction, Graph, Undirected};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A closed tour that visits every node of a graph once
+pub struct Tour{
+    /// The IDs of the nodes in the order they are visited.  The tour returns from the last
+    /// node to the first.
+    pub nodes: Vec<u32>,
+    /// The total weight of the edges on the tour, including the edge back to the start
+    pub cost: i64
+}
+
+impl<T, D: Direction> Graph<T, i64, D>{
+
+    /// Find the cheapest tour that visits every node once and returns to the start, using the
+    /// Held-Karp dynamic programming algorithm.  The tour starts at the smallest node ID, and
+    /// if there are several edges between two nodes the lightest one is used.  Directed edges
+    /// are followed in their direction only.  Returns `None` if there is no such tour.  Runs in
+    /// O(2^n n^2) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph has more than 16 nodes.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph of four towns on a square with long diagonals
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    /// let node4 = graph.add_node(4);
+    ///
+    /// graph.add_weighted_undirected_edge(node1, node2, 1);
+    /// graph.add_weighted_undirected_edge(node2, node3, 1);
+    /// graph.add_weighted_undirected_edge(node3, node4, 1);
+    /// graph.add_weighted_undirected_edge(node4, node1, 1);
+    /// graph.add_weighted_undirected_edge(node1, node3, 5);
+    /// graph.add_weighted_undirected_edge(node2, node4, 5);
+    ///
+    /// let tour = graph.held_karp().unwrap();
+    ///
+    /// assert_eq!(tour.nodes, vec![node1, node4, node3, node2]);
+    /// assert_eq!(tour.cost, 4);
+    /// ```
+    pub fn held_karp(&self) -> Option<Tour>{
+        let node_ids = self.sorted_node_ids();
+        assert!(node_ids.len() <= 16, "held_karp only supports graphs with at most 16 nodes");
+
+        if node_ids.len() < 2{
+            return Some(Tour{nodes: node_ids, cost: 0});
+        }
+
+        let distances = self.distance_matrix(&node_ids);
+
+        // Every node but the start is a bit in the set of visited nodes
+        let others = node_ids.len() - 1;
+        let full = (1 << others) - 1;
+        let mut costs = vec![vec![i64::MAX; others]; 1 << others];
+        let mut previous = vec![vec![None; others]; 1 << others];
+
+        for last in 0..others{
+            if let Some(distance) = distances[0][last + 1]{
+                costs[1 << last][last] = distance;
+            }
+        }
+
+        // Each entry is the cheapest path from the start through the visited set ending at `last`
+        for visited in 1..full + 1{
+            for last in 0..others{
+                if costs[visited][last] == i64::MAX{
+                    continue;
+                }
+
+                for next in 0..others{
+                    if visited & (1 << next) != 0{
+                        continue;
+                    }
+
+                    if let Some(distance) = distances[last + 1][next + 1]{
+                        let cost = costs[visited][last] + distance;
+                        let extended = visited | (1 << next);
+
+                        if cost < costs[extended][next]{
+                            costs[extended][next] = cost;
+                            previous[extended][next] = Some(last);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut best: Option<(i64, usize)> = None;
+        for last in 0..others{
+            if costs[full][last] == i64::MAX{
+                continue;
+            }
+
+            if let Some(distance) = distances[last + 1][0]{
+                let cost = costs[full][last] + distance;
+                if best.is_none_or(|(best_cost, _)| cost < best_cost){
+                    best = Some((cost, last));
+                }
+            }
+        }
+
+        let (cost, mut last) = best?;
+
+        // Follow the choices back to the start
+        let mut nodes = Vec::new();
+        let mut visited = full;
+        loop{
+            nodes.push(node_ids[last + 1]);
+            match previous[visited][last]{
+                Some(before) => {
+                    visited &= !(1 << last);
+                    last = before;
+                },
+                None => break
+            }
+        }
+        nodes.push(node_ids[0]);
+        nodes.reverse();
+
+        Some(Tour{nodes, cost})
+    }
+
+    /// Build a tour by starting at a node and always moving to the closest node not yet
+    /// visited, breaking ties by the smaller ID.  This is fast but can be far from the best
+    /// tour.  Returns `None` if `start` is not in the graph or the search gets stuck.
+    /// # Arguments
+    ///
+    /// `start` - The ID of the node the tour starts at
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    ///
+    /// graph.add_weighted_undirected_edge(node1, node2, 1);
+    /// graph.add_weighted_undirected_edge(node2, node3, 2);
+    /// graph.add_weighted_undirected_edge(node3, node1, 4);
+    ///
+    /// let tour = graph.nearest_neighbour_tour(node3).unwrap();
+    ///
+    /// assert_eq!(tour.nodes, vec![node3, node2, node1]);
+    /// assert_eq!(tour.cost, 7);
+    /// ```
+    pub fn nearest_neighbour_tour(&self, start: u32) -> Option<Tour>{
+        let node_ids = self.sorted_node_ids();
+        let start = node_ids.binary_search(&start).ok()?;
+        let distances = self.distance_matrix(&node_ids);
+
+        let mut visited = vec![false; node_ids.len()];
+        let mut order = vec![start];
+        visited[start] = true;
+
+        while order.len() < node_ids.len(){
+            let current = *order.last().unwrap();
+
+            let next = (0..node_ids.len())
+                .filter(|&node| !visited[node])
+                .filter_map(|node| distances[current][node].map(|distance| (distance, node)))
+                .min()?
+                .1;
+
+            visited[next] = true;
+            order.push(next);
+        }
+
+        let cost = tour_cost(&distances, &order)?;
+        Some(Tour{nodes: order.iter().map(|&node| node_ids[node]).collect(), cost})
+    }
+
+    /// Build a matrix of the lightest edge weight from each node to each other node, indexed
+    /// by position in `node_ids`.  Self loops are left out.
+    fn distance_matrix(&self, node_ids: &[u32]) -> Vec<Vec<Option<i64>>>{
+        let indices: HashMap<u32, usize> = node_ids.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+        let mut distances = vec![vec![None; node_ids.len()]; node_ids.len()];
+
+        for (i, node_id) in node_ids.iter().enumerate(){
+            for edge in self.edges(*node_id){
+                if let Some(&j) = indices.get(&edge.to){
+                    if i != j && distances[i][j].is_none_or(|distance| edge.weight < distance){
+                        distances[i][j] = Some(edge.weight);
+                    }
+                }
+            }
+        }
+
+        distances
+    }
+}
+
+impl<T> Graph<T, i64, Undirected>{
+    /// Improve a tour with 2-opt moves, reversing a section of the tour whenever that swaps two
+    /// of its edges for two cheaper ones, until no such move is left.  Returns
+    /// the tour unchanged if it can not be improved.
+    /// # Arguments
+    ///
+    /// `tour` - The tour to improve, which must visit every node of the graph once
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// # use kyles_algorithm_crate::graph::tsp::Tour;
+    /// // Create a graph of four towns on a square with long diagonals
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    /// let node4 = graph.add_node(4);
+    ///
+    /// graph.add_weighted_undirected_edge(node1, node2, 1);
+    /// graph.add_weighted_undirected_edge(node2, node3, 1);
+    /// graph.add_weighted_undirected_edge(node3, node4, 1);
+    /// graph.add_weighted_undirected_edge(node4, node1, 1);
+    /// graph.add_weighted_undirected_edge(node1, node3, 5);
+    /// graph.add_weighted_undirected_edge(node2, node4, 5);
+    ///
+    /// // A tour that crosses both diagonals
+    /// let crossed = Tour{nodes: vec![node1, node3, node2, node4], cost: 12};
+    ///
+    /// let tour = graph.two_opt(&crossed);
+    ///
+    /// assert_eq!(tour.nodes, vec![node1, node2, node3, node4]);
+    /// assert_eq!(tour.cost, 4);
+    /// ```
+    pub fn two_opt(&self, tour: &Tour) -> Tour{
+        let node_ids = self.sorted_node_ids();
+        let distances = self.distance_matrix(&node_ids);
+
+        let mut order: Vec<usize> = match tour.nodes.iter().map(|node_id| node_ids.binary_search(node_id).ok()).collect(){
+            Some(order) => order,
+            None => return tour.clone()
+        };
+        let size = order.len();
+
+        let mut improved = true;
+        while improved{
+            improved = false;
+
+            for i in 0..size{
+                for j in i + 2..size{
+                    // These two edges share a node when they wrap around the end of the tour
+                    if i == 0 && j == size - 1{
+                        continue;
+                    }
+
+                    let (a, b) = (order[i], order[i + 1]);
+                    let (c, d) = (order[j], order[(j + 1) % size]);
+
+                    let replaced = match (distances[a][b], distances[c][d]){
+                        (Some(first), Some(second)) => first + second,
+                        _ => continue
+                    };
+                    let added = match (distances[a][c], distances[b][d]){
+                        (Some(first), Some(second)) => first + second,
+                        _ => continue
+                    };
+
+                    if added < replaced{
+                        order[i + 1..j + 1].reverse();
+                        improved = true;
+                    }
+                }
+            }
+        }
+
+        match tour_cost(&distances, &order){
+            Some(cost) if cost < tour.cost => Tour{nodes: order.iter().map(|&node| node_ids[node]).collect(), cost},
+            _ => tour.clone()
+        }
+    }
+
+    /// Build a tour with Christofides' algorithm.  A minimum spanning tree is joined with a
+    /// minimum weight perfect matching of its odd degree nodes, an Eulerian circuit of the
+    /// result is found, and repeated nodes are skipped.  Every pair of nodes must have an edge.
+    /// When the weights obey the triangle inequality the tour costs at most 3/2 times the best
+    /// tour.  The matching is exact for up to 20 odd degree nodes and greedy beyond that, where
+    /// the bound no longer holds.  Returns `None` if some pair of nodes has no edge.
+    ///
+    /// # Example
+    /// ```
+    /// # use kyles_algorithm_crate::graph::Graph;
+    /// // Create a graph of four towns on a square with long diagonals
+    /// let mut graph = Graph::new_undirected();
+    ///
+    /// let node1 = graph.add_node(1);
+    /// let node2 = graph.add_node(2);
+    /// let node3 = graph.add_node(3);
+    /// let node4 = graph.add_node(4);
+    ///
+    /// graph.add_weighted_undirected_edge(node1, node2, 1);
+    /// graph.add_weighted_undirected_edge(node2, node3, 1);
+    /// graph.add_weighted_undirected_edge(node3, node4, 1);
+    /// graph.add_weighted_undirected_edge(node4, node1, 1);
+    /// graph.add_weighted_undirected_edge(node1, node3, 2);
+    /// graph.add_weighted_undirected_edge(node2, node4, 2);
+    ///
+    /// let tour = graph.christofides().unwrap();
+    ///
+    /// assert_eq!(tour.cost, 4);
+    /// ```
+    pub fn christofides(&self) -> Option<Tour>{
+        let node_ids = self.sorted_node_ids();
+        let distances = self.distance_matrix(&node_ids);
+
+        let complete = distances.iter().enumerate().all(|(i, row)| row.iter().enumerate().all(|(j, distance)| i == j || distance.is_some()));
+        if !complete{
+            return None;
+        }
+
+        let indices: HashMap<u32, usize> = node_ids.iter().enumerate().map(|(i, &node_id)| (node_id, i)).collect();
+
+        // Join the spanning tree and the matching into one graph where every node has even degree
+        let mut multigraph = Graph::new_undirected();
+        let mut degrees = vec![0; node_ids.len()];
+        for i in 0..node_ids.len(){
+            multigraph.add_node(i);
+        }
+
+        for (a, b, _) in self.kruskal().edges{
+            multigraph.add_undirected_edge(indices[&a] as u32, indices[&b] as u32);
+            degrees[indices[&a]] += 1;
+            degrees[indices[&b]] += 1;
+        }
+
+        let odd: Vec<usize> = (0..node_ids.len()).filter(|&node| degrees[node] % 2 == 1).collect();
+        for (a, b) in min_weight_matching(&distances, &odd){
+            multigraph.add_undirected_edge(a as u32, b as u32);
+        }
+
+        let circuit = multigraph.eulerian_circuit().ok()?;
+
+        // Skipping nodes that were already visited never makes the tour longer under the triangle inequality
+        let mut seen = vec![false; node_ids.len()];
+        let mut order = Vec::new();
+        for node in circuit{
+            if !seen[node as usize]{
+                seen[node as usize] = true;
+                order.push(node as usize);
+            }
+        }
+
+        if order.is_empty(){
+            order = (0..node_ids.len()).collect();
+        }
+
+        let cost = tour_cost(&distances, &order)?;
+        Some(Tour{nodes: order.iter().map(|&node| node_ids[node]).collect(), cost})
+    }
+}
+
+
+/// Add up the edges of a closed tour given by index, or `None` if one of them is missing
+fn tour_cost(distances: &[Vec<Option<i64>>], order: &[usize]) -> Option<i64>{
+    if order.len() < 2{
+        return Some(0);
+    }
+
+    (0..order.len()).map(|i| distances[order[i]][order[(i + 1) % order.len()]]).sum()
+}
+
+/// Pair up an even number of nodes so the total distance between pairs is as small as
+/// possible.  Small sets are solved exactly over every subset, larger ones greedily.
+fn min_weight_matching(distances: &[Vec<Option<i64>>], nodes: &[usize]) -> Vec<(usize, usize)>{
+    let distance = |a: usize, b: usize| distances[a][b].unwrap();
+
+    if nodes.len() > 20{
+        let mut pairs = Vec::new();
+        for (i, &a) in nodes.iter().enumerate(){
+            for &b in &nodes[i + 1..]{
+                pairs.push((distance(a, b), a, b));
+            }
+        }
+        pairs.sort();
+
+        let mut matched = vec![false; distances.len()];
+        let mut matching = Vec::new();
+        for (_, a, b) in pairs{
+            if !matched[a] && !matched[b]{
+                matched[a] = true;
+                matched[b] = true;
+                matching.push((a, b));
+            }
+        }
+        return matching;
+    }
+
+    // Each entry is the cheapest way to match the set of nodes, always pairing its lowest
+    // unmatched node next
+    let full = (1usize << nodes.len()) - 1;
+    let mut costs = vec![i64::MAX; full + 1];
+    let mut choices = vec![(0, 0); full + 1];
+    costs[0] = 0;
+
+    for matched in 0..full{
+        if costs[matched] == i64::MAX{
+            continue;
+        }
+
+        let first = (!matched).trailing_zeros() as usize;
+        for second in first + 1..nodes.len(){
+            if matched & (1 << second) != 0{
+                continue;
+            }
+
+            let extended = matched | (1 << first) | (1 << second);
+            let cost = costs[matched] + distance(nodes[first], nodes[second]);
+            if cost < costs[extended]{
+                costs[extended] = cost;
+                choices[extended] = (first, second);
+            }
+        }
+    }
+
+    let mut matching = Vec::new();
+    let mut matched = full;
+    while matched != 0{
+        let (first, second) = choices[matched];
+        matching.push((nodes[first], nodes[second]));
+        matched &= !((1 << first) | (1 << second));
+    }
+
+    matching
+}
+
+#[cfg(test)]
+mod tests {
+    use graph::{Graph, Undirected};
+    use graph::test_util::Lcg;
+
+    /// Build a complete undirected graph of points on a grid, weighted by Manhattan distance
+    fn points_graph(points: &[(i64, i64)]) -> Graph<(i64, i64), i64, Undirected>{
+        let mut graph = Graph::new_undirected();
+
+        for &point in points{
+            graph.add_node(point);
+        }
+
+        for i in 0..points.len(){
+            for j in i + 1..points.len(){
+                let distance = (points[i].0 - points[j].0).abs() + (points[i].1 - points[j].1).abs();
+                graph.add_weighted_undirected_edge(i as u32, j as u32, distance);
+            }
+        }
+
+        graph
+    }
+
+    /// Find the cheapest tour by trying every order of the nodes after the first
+    fn brute_force(graph: &Graph<(i64, i64), i64, Undirected>) -> i64{
+        let size = graph.node_count();
+        let weight = |a: usize, b: usize| *graph.edges(a as u32).find(|edge| edge.to() == b as u32).unwrap().weight();
+
+        let mut order: Vec<usize> = (1..size).collect();
+        let mut best = i64::MAX;
+        loop{
+            let mut cost = weight(0, order[0]) + weight(order[size - 2], 0);
+            for pair in order.windows(2){
+                cost += weight(pair[0], pair[1]);
+            }
+            best = best.min(cost);
+
+            // Step to the next permutation in lexicographic order
+            let pivot = match (0..order.len() - 1).rev().find(|&i| order[i] < order[i + 1]){
+                Some(pivot) => pivot,
+                None => return best
+            };
+            let swap = (pivot + 1..order.len()).rev().find(|&i| order[i] > order[pivot]).unwrap();
+            order.swap(pivot, swap);
+            order[pivot + 1..].reverse();
+        }
+    }
+
+    /// Check that a tour visits every node once and its cost matches its edges
+    fn check_tour(graph: &Graph<(i64, i64), i64, Undirected>, nodes: &[u32], cost: i64){
+        let mut sorted = nodes.to_vec();
+        sorted.sort();
+        assert_eq!(sorted, graph.sorted_node_ids());
+
+        let total: i64 = (0..nodes.len()).map(|i| {
+            let (from, to) = (nodes[i], nodes[(i + 1) % nodes.len()]);
+            *graph.edges(from).find(|edge| edge.to() == to).unwrap().weight()
+        }).sum();
+        assert_eq!(total, cost);
+    }
+
+    fn random_points(size: usize, seed: u64) -> Vec<(i64, i64)>{
+        let mut random = Lcg::new(seed);
+        (0..size).map(|_| (random.in_range(0..50), random.in_range(0..50))).collect()
+    }
+
+    #[test]
+    fn test_held_karp(){
+        for seed in 0..10{
+            let graph = points_graph(&random_points(3 + seed as usize % 6, seed));
+            let tour = graph.held_karp().unwrap();
+
+            check_tour(&graph, &tour.nodes, tour.cost);
+            assert_eq!(tour.nodes[0], 0);
+            assert_eq!(tour.cost, brute_force(&graph));
+        }
+    }
+
+    #[test]
+    fn test_held_karp_directed(){
+        let mut graph = Graph::new();
+
+        let zeroth_node = graph.add_node(0);
+        let first_node = graph.add_node(1);
+        let second_node = graph.add_node(2);
+
+        // Only one way round the triangle is possible
+        graph.add_weighted_directed_edge(zeroth_node, second_node, 2);
+        graph.add_weighted_directed_edge(second_node, first_node, 3);
+        graph.add_weighted_directed_edge(first_node, zeroth_node, 4);
+        graph.add_weighted_directed_edge(first_node, zeroth_node, 1);
+
+        let tour = graph.held_karp().unwrap();
+
+        assert_eq!(tour.nodes, vec![zeroth_node, second_node, first_node]);
+        assert_eq!(tour.cost, 6);
+
+        let tour = graph.nearest_neighbour_tour(first_node).unwrap();
+
+        assert_eq!(tour.nodes, vec![first_node, zeroth_node, second_node]);
+        assert_eq!(tour.cost, 6);
+        assert!(graph.nearest_neighbour_tour(7).is_none());
+
+        // Two nodes without a way back have no tour
+        let mut graph2 = Graph::new();
+
+        let node1_id = graph2.add_node(1);
+        let node2_id = graph2.add_node(2);
+        graph2.add_directed_edge(node1_id, node2_id);
+
+        assert!(graph2.held_karp().is_none());
+
+        // A single node is a tour on its own
+        let mut graph3 = Graph::new_undirected();
+
+        let node1_id = graph3.add_node(1);
+
+        assert_eq!(graph3.held_karp().unwrap().nodes, vec![node1_id]);
+        assert_eq!(graph3.christofides().unwrap().nodes, vec![node1_id]);
+    }
+
+    #[test]
+    fn test_heuristic_tours(){
+        for seed in 0..10{
+            let graph = points_graph(&random_points(4 + seed as usize % 5, seed));
+            let best = graph.held_karp().unwrap().cost;
+
+            let nearest = graph.nearest_neighbour_tour(0).unwrap();
+            check_tour(&graph, &nearest.nodes, nearest.cost);
+            assert!(nearest.cost >= best);
+
+            let improved = graph.two_opt(&nearest);
+            check_tour(&graph, &improved.nodes, improved.cost);
+            assert!(improved.cost >= best);
+            assert!(improved.cost <= nearest.cost);
+
+            // Manhattan distances obey the triangle inequality, so the 3/2 bound holds
+            let christofides = graph.christofides().unwrap();
+            check_tour(&graph, &christofides.nodes, christofides.cost);
+            assert!(christofides.cost >= best);
+            assert!(2 * christofides.cost <= 3 * best);
+        }
+    }
+
+    #[test]
+    fn test_large_heuristic_tours(){
+        let graph = points_graph(&random_points(60, 42));
+
+        let nearest = graph.nearest_neighbour_tour(0).unwrap();
+        let improved = graph.two_opt(&nearest);
+        let christofides = graph.christofides().unwrap();
+
+        check_tour(&graph, &improved.nodes, improved.cost);
+        check_tour(&graph, &christofides.nodes, christofides.cost);
+        assert!(improved.cost <= nearest.cost);
+
+        // No tour is shorter than the minimum spanning tree
+        let tree_weight = graph.kruskal().total_weight;
+        assert!(improved.cost >= tree_weight);
+        assert!(christofides.cost >= tree_weight);
+        assert!(christofides.cost <= 2 * tree_weight);
+    }
+}
@@ -0,0 +1,137 @@
+
+/// Disjoint set forest over the elements `0..n`, using union by rank and path compression
+#[derive(Debug, Clone)]
+pub struct UnionFind{
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sets: usize
+}
+
+impl UnionFind{
+
+    /// Returns a new instance of a UnionFind where each of the `size` elements is in its own set
+    /// # Arguments
+    ///
+    /// * `size` - The number of elements
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use kyles_algorithm_crate::union_find::UnionFind;
+    /// // Creates five elements in five sets
+    /// let sets = UnionFind::new(5);
+    /// ```
+    pub fn new(size: usize) -> Self{
+        UnionFind{parents: (0..size).collect(), ranks: vec![0; size], sets: size}
+    }
+
+    /// Returns the representative element of the set containing `element`.
+    /// Two elements are in the same set exactly when they have the same representative.
+    /// # Arguments
+    ///
+    /// * `element` - The element to find the set of
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use kyles_algorithm_crate::union_find::UnionFind;
+    /// let mut sets = UnionFind::new(5);
+    ///
+    /// sets.union(1, 3);
+    ///
+    /// assert_eq!(sets.find(1), sets.find(3));
+    /// assert_ne!(sets.find(1), sets.find(2));
+    /// ```
+    pub fn find(&mut self, element: usize) -> usize{
+        let mut root = element;
+        while self.parents[root] != root{
+            root = self.parents[root];
+        }
+
+        // Point everything on the way straight at the root
+        let mut current = element;
+        while self.parents[current] != root{
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    /// Merges the sets containing two elements.  Returns false if they were already in the same set.
+    /// # Arguments
+    ///
+    /// * `a` - An element of the first set
+    /// * `b` - An element of the second set
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use kyles_algorithm_crate::union_find::UnionFind;
+    /// let mut sets = UnionFind::new(5);
+    ///
+    /// assert!(sets.union(1, 3));
+    /// assert!(!sets.union(3, 1));
+    /// ```
+    pub fn union(&mut self, a: usize, b: usize) -> bool{
+        let root_a = self.find(a);
+        let root_b = self.find(b);
+
+        if root_a == root_b{
+            return false;
+        }
+
+        // Hang the shorter tree under the taller one
+        if self.ranks[root_a] < self.ranks[root_b]{
+            self.parents[root_a] = root_b;
+        }else if self.ranks[root_a] > self.ranks[root_b]{
+            self.parents[root_b] = root_a;
+        }else{
+            self.parents[root_b] = root_a;
+            self.ranks[root_a] += 1;
+        }
+
+        self.sets -= 1;
+        true
+    }
+
+    /// Returns the number of disjoint sets
+    pub fn sets(&self) -> usize{
+        self.sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test_find(){
+        use union_find::UnionFind;
+
+        let mut sets = UnionFind::new(3);
+
+        assert_eq!(sets.find(0), 0);
+        assert_eq!(sets.find(1), 1);
+        assert_eq!(sets.find(2), 2);
+    }
+
+    #[test]
+    fn test_union(){
+        use union_find::UnionFind;
+
+        let mut sets = UnionFind::new(6);
+
+        assert_eq!(sets.sets(), 6);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+
+        assert_eq!(sets.sets(), 3);
+        assert_eq!(sets.find(0), sets.find(3));
+        assert_ne!(sets.find(0), sets.find(4));
+        assert_ne!(sets.find(4), sets.find(5));
+    }
+}